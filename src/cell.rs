@@ -0,0 +1,151 @@
+use crate::movement::Direction;
+
+/// Conversion of a maze element into the character used to display it.
+pub trait AsChar {
+    /// Returns the display character of `self`.
+    fn as_char(&self) -> char;
+}
+
+/// Single cell of a [`Maze`](crate::Maze).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum MazeCell {
+    /// Impassable cell.
+    #[default]
+    Wall,
+    /// Passable cell of the given [`FloorType`].
+    Floor(FloorType),
+}
+
+impl AsChar for MazeCell {
+    fn as_char(&self) -> char {
+        match self {
+            MazeCell::Wall => '⬜',
+            MazeCell::Floor(f) => f.as_char(),
+        }
+    }
+}
+
+impl MazeCell {
+    /// Colour of this cell in images, as red, green and blue.
+    pub(crate) fn colour(&self) -> [u8; 3] {
+        match self {
+            MazeCell::Wall => [0x33, 0x33, 0x33],
+            MazeCell::Floor(floor) => match floor {
+                FloorType::Floor => [0xff, 0xff, 0xff],
+                FloorType::Start => [0x2e, 0x7d, 0x32],
+                FloorType::Exit => [0xc6, 0x28, 0x28],
+                FloorType::Path => [0xff, 0x98, 0x00],
+                FloorType::Road => [0xf2, 0xd1, 0x6b],
+                FloorType::Mud => [0x8b, 0x5a, 0x2b],
+                FloorType::Water => [0x4a, 0x90, 0xd9],
+                FloorType::Stairs => [0x9e, 0x9e, 0x9e],
+                FloorType::Ice => [0xb3, 0xe5, 0xfc],
+                FloorType::Portal(_) => [0x8e, 0x44, 0xad],
+                FloorType::Key(_) => [0x00, 0xbc, 0xd4],
+                FloorType::Door(_) => [0x5d, 0x40, 0x37],
+                FloorType::OneWay(_) => [0xc5, 0xe1, 0xa5],
+                FloorType::Conveyor(_) => [0x7c, 0xb3, 0x42],
+            },
+        }
+    }
+
+    pub(crate) fn from_bool(value: bool) -> Self {
+        if value {
+            MazeCell::Floor(FloorType::default())
+        } else {
+            MazeCell::default()
+        }
+    }
+}
+
+/// Kind of a passable [`MazeCell`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FloorType {
+    /// Plain floor.
+    #[default]
+    Floor,
+    /// Starting position, only used for display.
+    Start,
+    /// Explicit exit, only used for display.
+    Exit,
+    /// Part of a solution.
+    Path,
+    /// Paved road, the cheapest terrain to cross but more expensive than plain floor.
+    Road,
+    /// Mud, more expensive to cross than plain floor.
+    Mud,
+    /// Shallow water, the most expensive terrain to cross.
+    Water,
+    /// Stairs to the same position on the levels above and below of a
+    /// [`LayeredMaze`](crate::LayeredMaze), plain floor in a single level.
+    Stairs,
+    /// Ice, a walker stepping onto it slides on in the same direction until blocked.
+    Ice,
+    /// Portal that takes the walker to the other cell with the same label at no cost.
+    Portal(char),
+    /// Key that opens every [`FloorType::Door`] with the same label once the walker stepped on
+    /// it, labels are `'a'` to `'z'`.
+    Key(char),
+    /// Door that is only passable while holding the [`FloorType::Key`] with the same label.
+    Door(char),
+    /// One-way cell that can only be left in its [`Direction`].
+    OneWay(Direction),
+    /// Conveyor that moves the walker on in its [`Direction`] at no cost, the only way to leave
+    /// it.
+    Conveyor(Direction),
+}
+
+impl FloorType {
+    /// Cost of stepping onto a cell of this type.
+    ///
+    /// Plain floor costs `1`, so the cost of a route without terrain is its number of steps.
+    pub fn cost(&self) -> usize {
+        match self {
+            FloorType::Floor
+            | FloorType::Start
+            | FloorType::Exit
+            | FloorType::Path
+            | FloorType::Stairs
+            | FloorType::Ice
+            | FloorType::Portal(_)
+            | FloorType::Key(_)
+            | FloorType::Door(_)
+            | FloorType::OneWay(_)
+            | FloorType::Conveyor(_) => 1,
+            FloorType::Road => 2,
+            FloorType::Mud => 5,
+            FloorType::Water => 10,
+        }
+    }
+}
+
+impl AsChar for FloorType {
+    fn as_char(&self) -> char {
+        match self {
+            FloorType::Floor => '⬛',
+            FloorType::Start => '❌',
+            FloorType::Exit => '🏁',
+            FloorType::Path => '👣',
+            FloorType::Road => '🟨',
+            FloorType::Mud => '🟫',
+            FloorType::Water => '🟦',
+            FloorType::Stairs => '🪜',
+            FloorType::Ice => '🧊',
+            FloorType::Portal(_) => '🌀',
+            FloorType::Key(_) => '🔑',
+            FloorType::Door(_) => '🚪',
+            FloorType::OneWay(direction) => match direction {
+                Direction::Left => '👈',
+                Direction::Right => '👉',
+                Direction::Up => '👆',
+                Direction::Down => '👇',
+            },
+            FloorType::Conveyor(direction) => match direction {
+                Direction::Left => '⏪',
+                Direction::Right => '⏩',
+                Direction::Up => '⏫',
+                Direction::Down => '⏬',
+            },
+        }
+    }
+}
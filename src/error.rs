@@ -0,0 +1,134 @@
+use std::error;
+use std::fmt;
+
+/// Errors returned while creating or solving a [`Maze`](crate::Maze).
+#[derive(Clone, Debug, PartialEq)]
+pub enum MazeError {
+    /// The maze is smaller than 3x3.
+    TooSmall { width: usize, height: usize },
+    /// The starting position is on a wall.
+    StartOnWall { x: usize, y: usize },
+    /// The starting position is outside of the maze.
+    StartOutOfBounds { x: usize, y: usize },
+    /// The maze data contains no start marker and no starting position was given.
+    MissingStart,
+    /// The maze data contains more than one start marker, the second one is at `x`, `y`.
+    MultipleStarts { x: usize, y: usize },
+    /// An exit is outside of the maze.
+    ExitOutOfBounds { x: usize, y: usize },
+    /// An exit is on a wall.
+    ExitOnWall { x: usize, y: usize },
+    /// The maze data contains a character that is not understood.
+    ///
+    /// `line` and `column` are 1-based.
+    UnknownCharacter {
+        character: char,
+        line: usize,
+        column: usize,
+    },
+    /// A line after the rows of the maze data is not a marker line like `@path 1,2 1,3`.
+    ///
+    /// `line` is 1-based.
+    InvalidMarkers { line: usize },
+    /// No goal cells were given to a goal-directed solver.
+    NoGoals,
+    /// A goal cell is outside of the maze.
+    GoalOutOfBounds { x: usize, y: usize },
+    /// A goal cell is on a wall.
+    GoalOnWall { x: usize, y: usize },
+    /// A position of a path is outside of the maze.
+    PathOutOfBounds { x: usize, y: usize },
+    /// A position of a path is on a wall.
+    PathOnWall { x: usize, y: usize },
+    /// A maze without a border, like a [`Topology::Torus`](crate::Topology::Torus), has no
+    /// explicit exits.
+    MissingExits,
+    /// A [`Shape::Hex`](crate::Shape::Hex) maze on a [`Topology::Torus`](crate::Topology::Torus)
+    /// has an odd height, so its shifted rows don't line up across the top and bottom edges.
+    OddHexTorus { height: usize },
+    /// A portal label is not used by exactly two cells.
+    UnpairedPortal { portal: char },
+    /// A circular maze needs at least 2 rings.
+    TooFewRings { rings: usize },
+    /// An image needs at least one pixel per cell.
+    InvalidCellSize { size: usize },
+    /// An image with cells of `cell_size` pixels would be larger than its format allows.
+    ImageTooLarge { cell_size: usize },
+    /// Image data is damaged or uses a format that is not supported.
+    InvalidImage { reason: &'static str },
+    /// `error` at a position on level `z` of a [`LayeredMaze`](crate::LayeredMaze).
+    OnLevel { z: usize, error: Box<MazeError> },
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MazeError::TooSmall { width, height } => {
+                write!(f, "Maze is too small ({}x{}). Minimum 3x3", width, height)
+            }
+            MazeError::StartOnWall { x, y } => {
+                write!(f, "Starting position ({}, {}) must not be on a wall!", x, y)
+            }
+            MazeError::StartOutOfBounds { x, y } => {
+                write!(f, "Starting position ({}, {}) out of bounds", x, y)
+            }
+            MazeError::MissingStart => write!(f, "No starting position given!"),
+            MazeError::MultipleStarts { x, y } => {
+                write!(f, "Second starting position at ({}, {})!", x, y)
+            }
+            MazeError::ExitOutOfBounds { x, y } => write!(f, "Exit ({}, {}) out of bounds", x, y),
+            MazeError::ExitOnWall { x, y } => {
+                write!(f, "Exit ({}, {}) must not be on a wall!", x, y)
+            }
+            MazeError::UnknownCharacter {
+                character,
+                line,
+                column,
+            } => write!(
+                f,
+                "Unknown character '{}' in provided maze data at line {}, column {}!",
+                character, line, column
+            ),
+            MazeError::InvalidMarkers { line } => {
+                write!(f, "Invalid marker line {} in provided maze data!", line)
+            }
+            MazeError::NoGoals => write!(f, "No goal cells given!"),
+            MazeError::GoalOutOfBounds { x, y } => {
+                write!(f, "Goal ({}, {}) out of bounds", x, y)
+            }
+            MazeError::GoalOnWall { x, y } => {
+                write!(f, "Goal ({}, {}) must not be on a wall!", x, y)
+            }
+            MazeError::PathOutOfBounds { x, y } => {
+                write!(f, "Path position ({}, {}) out of bounds", x, y)
+            }
+            MazeError::PathOnWall { x, y } => {
+                write!(f, "Path position ({}, {}) is on a wall!", x, y)
+            }
+            MazeError::MissingExits => write!(f, "Maze without a border needs explicit exits!"),
+            MazeError::OddHexTorus { height } => {
+                write!(
+                    f,
+                    "Hex maze on a torus needs an even height, not {}!",
+                    height
+                )
+            }
+            MazeError::UnpairedPortal { portal } => {
+                write!(f, "Portal '{}' needs exactly one partner!", portal)
+            }
+            MazeError::TooFewRings { rings } => {
+                write!(f, "Maze has too few rings ({}). Minimum 2", rings)
+            }
+            MazeError::InvalidCellSize { size } => {
+                write!(f, "Invalid cell size ({}). Minimum 1 pixel", size)
+            }
+            MazeError::ImageTooLarge { cell_size } => {
+                write!(f, "Image with cell size {} is too large!", cell_size)
+            }
+            MazeError::InvalidImage { reason } => write!(f, "Invalid image: {}!", reason),
+            MazeError::OnLevel { z, error } => write!(f, "Level {}: {}", z, error),
+        }
+    }
+}
+
+impl error::Error for MazeError {}
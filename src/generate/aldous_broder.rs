@@ -0,0 +1,49 @@
+use super::{Generator, Grid};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Generator of perfect mazes using the Aldous-Broder algorithm.
+///
+/// A random walk visits the whole grid and connects every cell it enters for the first time.
+/// The result is a uniform spanning tree, but generation gets slow for large mazes.
+#[derive(Clone, Debug)]
+pub struct AldousBroder {
+    seed: u64,
+}
+
+impl AldousBroder {
+    /// Creates a new [`AldousBroder`] generator with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Generator for AldousBroder {
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError> {
+        let mut grid = Grid::new(width, height, topology)?;
+        let mut rng = Rng::new(self.seed);
+
+        let mut cell = (rng.below(width), rng.below(height));
+        grid.carve(cell);
+        let mut remaining = width * height - 1;
+
+        while remaining > 0 {
+            let neighbours = grid.neighbours(cell);
+            let next = neighbours[rng.below(neighbours.len())];
+            if !grid.is_carved(next) {
+                grid.link(cell, next);
+                remaining -= 1;
+            }
+            cell = next;
+        }
+
+        grid.into_maze(&mut rng)
+    }
+}
@@ -0,0 +1,51 @@
+use super::{Generator, Grid};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Generator of perfect mazes using the recursive backtracker algorithm.
+///
+/// Starting from a random cell it walks to random unvisited neighbours and backtracks at dead
+/// ends, which results in long winding corridors.
+#[derive(Clone, Debug)]
+pub struct RecursiveBacktracker {
+    seed: u64,
+}
+
+impl RecursiveBacktracker {
+    /// Creates a new [`RecursiveBacktracker`] with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Generator for RecursiveBacktracker {
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError> {
+        let mut grid = Grid::new(width, height, topology)?;
+        let mut rng = Rng::new(self.seed);
+
+        let start = (rng.below(width), rng.below(height));
+        grid.carve(start);
+        let mut stack = vec![start];
+
+        while let Some(&cell) = stack.last() {
+            match rng.choose(&grid.neighbours_carved(cell, false)) {
+                Some(&next) => {
+                    grid.link(cell, next);
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+
+        grid.into_maze(&mut rng)
+    }
+}
@@ -0,0 +1,45 @@
+use super::{Generator, Grid};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Generator of perfect mazes using the Binary Tree algorithm.
+///
+/// Every cell connects either upwards or to the left. Fast and simple, but the top row and the
+/// left column are long corridors and routes are biased towards the top left corner.
+#[derive(Clone, Debug)]
+pub struct BinaryTree {
+    seed: u64,
+}
+
+impl BinaryTree {
+    /// Creates a new [`BinaryTree`] generator with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Generator for BinaryTree {
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError> {
+        let mut grid = Grid::new(width, height, topology)?;
+        let mut rng = Rng::new(self.seed);
+
+        for (x, y) in grid.cells() {
+            grid.carve((x, y));
+            match (x > 0, y > 0) {
+                (true, true) if rng.coin() => grid.link((x, y), (x - 1, y)),
+                (_, true) => grid.link((x, y), (x, y - 1)),
+                (true, false) => grid.link((x, y), (x - 1, y)),
+                (false, false) => {}
+            }
+        }
+
+        grid.into_maze(&mut rng)
+    }
+}
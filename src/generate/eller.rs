@@ -0,0 +1,83 @@
+use super::{Generator, Grid};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Generator of perfect mazes using Eller's algorithm.
+///
+/// The maze is built row by row, only keeping track of which cells of the current row are
+/// already connected. This needs very little memory, even for extremely tall mazes.
+#[derive(Clone, Debug)]
+pub struct Eller {
+    seed: u64,
+}
+
+impl Eller {
+    /// Creates a new [`Eller`] generator with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Generator for Eller {
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError> {
+        let mut grid = Grid::new(width, height, topology)?;
+        let mut rng = Rng::new(self.seed);
+
+        // set of each cell in the next row, `None` if not connected to the row above
+        let mut below: Vec<Option<usize>> = vec![None; width];
+        let mut next_set = 0;
+
+        for y in 0..height {
+            let last_row = y + 1 == height;
+
+            // cells not connected to the row above start their own set
+            let mut sets: Vec<usize> = below
+                .iter()
+                .map(|set| {
+                    set.unwrap_or_else(|| {
+                        next_set += 1;
+                        next_set - 1
+                    })
+                })
+                .collect();
+
+            // randomly join neighbouring cells of different sets, the last row joins all of them
+            for x in 0..width.saturating_sub(1) {
+                if sets[x] != sets[x + 1] && (last_row || rng.coin()) {
+                    grid.link((x, y), (x + 1, y));
+                    let (merged, kept) = (sets[x + 1], sets[x]);
+                    for set in sets.iter_mut().filter(|set| **set == merged) {
+                        *set = kept;
+                    }
+                }
+            }
+            grid.carve((0, y));
+
+            if last_row {
+                break;
+            }
+
+            // every set continues down at least once
+            below = vec![None; width];
+            let mut columns: Vec<usize> = (0..width).collect();
+            rng.shuffle(&mut columns);
+            let mut continued: Vec<usize> = Vec::new();
+            for x in columns {
+                if !continued.contains(&sets[x]) || rng.coin() {
+                    continued.push(sets[x]);
+                    grid.link((x, y), (x, y + 1));
+                    below[x] = Some(sets[x]);
+                }
+            }
+        }
+
+        grid.into_maze(&mut rng)
+    }
+}
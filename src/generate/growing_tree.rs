@@ -0,0 +1,99 @@
+use super::{Generator, Grid};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Rule of [`GrowingTree`] to pick the next active cell to grow from.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CellSelection {
+    /// Most recently added cell, behaves like the
+    /// [`RecursiveBacktracker`](super::RecursiveBacktracker).
+    #[default]
+    Newest,
+    /// Least recently added cell, creates long straight corridors.
+    Oldest,
+    /// Random cell, behaves like [`Prim`](super::Prim).
+    Random,
+    /// Newest cell with the given chance in percent, a random one otherwise.
+    Mixed {
+        /// Chance in percent (`0..=100`) to pick the newest cell.
+        newest_percent: u8,
+    },
+}
+
+/// Generator of perfect mazes using the Growing Tree algorithm.
+///
+/// Keeps a list of active cells and grows the maze from one of them, chosen by its
+/// [`CellSelection`]. The selection rule controls the texture between long corridors and many
+/// short dead ends.
+#[derive(Clone, Debug)]
+pub struct GrowingTree {
+    seed: u64,
+    selection: CellSelection,
+}
+
+impl GrowingTree {
+    /// Creates a new [`GrowingTree`] generator with the given seed and the default
+    /// [`CellSelection`].
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            selection: CellSelection::default(),
+        }
+    }
+
+    /// Sets the [`CellSelection`] of this generator.
+    pub fn with_selection(mut self, selection: CellSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    fn select(&self, rng: &mut Rng, len: usize) -> usize {
+        match self.selection {
+            CellSelection::Newest => len - 1,
+            CellSelection::Oldest => 0,
+            CellSelection::Random => rng.below(len),
+            CellSelection::Mixed { newest_percent } => {
+                if rng.below(100) < usize::from(newest_percent) {
+                    len - 1
+                } else {
+                    rng.below(len)
+                }
+            }
+        }
+    }
+}
+
+impl Generator for GrowingTree {
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError> {
+        let mut grid = Grid::new(width, height, topology)?;
+        let mut rng = Rng::new(self.seed);
+
+        let start = (rng.below(width), rng.below(height));
+        grid.carve(start);
+        let mut active = vec![start];
+
+        while !active.is_empty() {
+            let index = self.select(&mut rng, active.len());
+            let cell = active[index];
+
+            match rng.choose(&grid.neighbours_carved(cell, false)) {
+                Some(&next) => {
+                    grid.link(cell, next);
+                    active.push(next);
+                }
+                None => {
+                    active.remove(index);
+                }
+            }
+        }
+
+        grid.into_maze(&mut rng)
+    }
+}
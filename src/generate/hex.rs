@@ -0,0 +1,109 @@
+use crate::cell::MazeCell;
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::{hex_neighbours, Shape};
+
+/// Generator of perfect [`Shape::Hex`] mazes using the recursive backtracker algorithm.
+///
+/// Hexagons have no room for walls between two cells, so the corridors are carved directly
+/// into the grid: a cell is only carved if it touches no other corridor than the one it is
+/// carved from. The floor cells therefore form a tree and there is exactly one route between
+/// any two of them.
+#[derive(Clone, Debug)]
+pub struct HexBacktracker {
+    seed: u64,
+}
+
+impl HexBacktracker {
+    /// Creates a new [`HexBacktracker`] with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Generates a perfect hex maze of `width` x `height` cells including the border.
+    ///
+    /// Unlike the [`Generator`](super::Generator)s the size is the size of the resulting
+    /// [`Maze`]. It starts in the top left cell inside the border and has one opening in the
+    /// right border.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `width` or `height` is smaller than `3`.
+    pub fn generate(&self, width: usize, height: usize) -> Result<Maze, MazeError> {
+        if width < 3 || height < 3 {
+            return Err(MazeError::TooSmall { width, height });
+        }
+        let mut rng = Rng::new(self.seed);
+        let mut floor = vec![vec![false; width]; height];
+
+        let is_inside = |(x, y): (usize, usize)| x > 0 && x < width - 1 && y > 0 && y < height - 1;
+        let floor_neighbours = |floor: &[Vec<bool>], (x, y): (usize, usize)| {
+            hex_neighbours(x, y)
+                .into_iter()
+                .filter(|&(x, y)| floor.get(y).and_then(|row| row.get(x)) == Some(&true))
+                .count()
+        };
+
+        floor[1][1] = true;
+        let mut stack = vec![(1, 1)];
+        while let Some(&(x, y)) = stack.last() {
+            let candidates: Vec<(usize, usize)> = hex_neighbours(x, y)
+                .into_iter()
+                .filter(|&next| {
+                    is_inside(next) && !floor[next.1][next.0] && floor_neighbours(&floor, next) == 1
+                })
+                .collect();
+            match rng.choose(&candidates) {
+                Some(&(next_x, next_y)) => {
+                    floor[next_y][next_x] = true;
+                    stack.push((next_x, next_y));
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+
+        // open the right border next to exactly one corridor, so the maze stays perfect
+        let mut rows: Vec<usize> = (1..height - 1).collect();
+        rng.shuffle(&mut rows);
+        let exit = rows
+            .iter()
+            .map(|&y| (width - 1, y))
+            .find(|&exit| floor_neighbours(&floor, exit) == 1);
+        match exit {
+            Some((x, y)) => floor[y][x] = true,
+            None => {
+                // no corridor reaches the border, tunnel from it along a row that touches a
+                // single corridor cell, more would close a loop
+                let tunnel = rows.iter().find_map(|&y| {
+                    let mut touched = Vec::new();
+                    for x in (1..width).rev() {
+                        for next in hex_neighbours(x, y) {
+                            let is_floor = floor.get(next.1).and_then(|row| row.get(next.0));
+                            if is_floor == Some(&true) && !touched.contains(&next) {
+                                touched.push(next);
+                            }
+                        }
+                        match touched.len() {
+                            0 => {}
+                            1 => return Some((x, y)),
+                            _ => return None,
+                        }
+                    }
+                    None
+                });
+                if let Some((end, y)) = tunnel {
+                    floor[y][end..].fill(true);
+                }
+            }
+        }
+
+        let map = floor
+            .into_iter()
+            .map(|row| row.into_iter().map(MazeCell::from_bool).collect())
+            .collect();
+        Ok(Maze::new(map, 1, 1)?.with_shape(Shape::Hex))
+    }
+}
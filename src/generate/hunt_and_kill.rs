@@ -0,0 +1,65 @@
+use super::{Generator, Grid};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Generator of perfect mazes using the Hunt-and-Kill algorithm.
+///
+/// Like the [`RecursiveBacktracker`](super::RecursiveBacktracker) it walks to random unvisited
+/// neighbours, but at a dead end it hunts for the first unvisited cell next to the maze instead
+/// of backtracking. Needs no stack and creates long corridors.
+#[derive(Clone, Debug)]
+pub struct HuntAndKill {
+    seed: u64,
+}
+
+impl HuntAndKill {
+    /// Creates a new [`HuntAndKill`] generator with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Generator for HuntAndKill {
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError> {
+        let mut grid = Grid::new(width, height, topology)?;
+        let mut rng = Rng::new(self.seed);
+
+        let mut current = Some((rng.below(width), rng.below(height)));
+        if let Some(cell) = current {
+            grid.carve(cell);
+        }
+
+        while let Some(cell) = current {
+            current = match rng.choose(&grid.neighbours_carved(cell, false)) {
+                Some(&next) => {
+                    // kill: walk on
+                    grid.link(cell, next);
+                    Some(next)
+                }
+                None => {
+                    // hunt: first unvisited cell next to the maze, row by row
+                    let found = grid.cells().into_iter().find_map(|cell| {
+                        if grid.is_carved(cell) {
+                            return None;
+                        }
+                        rng.choose(&grid.neighbours_carved(cell, true))
+                            .map(|&next| (cell, next))
+                    });
+                    found.map(|(cell, next)| {
+                        grid.link(cell, next);
+                        cell
+                    })
+                }
+            };
+        }
+
+        grid.into_maze(&mut rng)
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::VecDeque;
+
+use crate::cell::MazeCell;
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::movement::Movement;
+use crate::rng::Rng;
+
+/// Number of rock layouts tried by [`IcePuzzle::generate`].
+const ATTEMPTS: usize = 20;
+
+/// Generator of ice sliding puzzles with [`Movement::Sliding`].
+///
+/// Rocks are scattered over an open field inside a border of walls. Every move slides until the
+/// next rock or wall, so the walker has to find rocks to stop at on the way to the exit. The
+/// exit is a cell the walker can stop on, the one that needs the most moves.
+#[derive(Clone, Debug)]
+pub struct IcePuzzle {
+    seed: u64,
+    rock_percent: u8,
+}
+
+impl IcePuzzle {
+    /// Creates a new [`IcePuzzle`] generator with the given seed and 15% rocks.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rock_percent: 15,
+        }
+    }
+
+    /// Sets the chance in percent (`0..=100`) for each cell inside the border to be a rock.
+    pub fn with_rocks(mut self, rock_percent: u8) -> Self {
+        self.rock_percent = rock_percent;
+        self
+    }
+
+    /// Generates a solvable sliding puzzle of `width` x `height` cells including the border.
+    ///
+    /// Like for [`HexBacktracker::generate`](super::HexBacktracker::generate) the size is the
+    /// size of the resulting [`Maze`]. Several rock layouts are tried and the one whose
+    /// solution needs the most moves is kept, its exit is explicit.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `width` or `height` is smaller than `3`.
+    pub fn generate(&self, width: usize, height: usize) -> Result<Maze, MazeError> {
+        if width < 3 || height < 3 {
+            return Err(MazeError::TooSmall { width, height });
+        }
+        let mut rng = Rng::new(self.seed);
+
+        let mut best = self.layout(&mut rng, width, height)?;
+        for _ in 1..ATTEMPTS {
+            let next = self.layout(&mut rng, width, height)?;
+            if next.0 > best.0 {
+                best = next;
+            }
+        }
+        let (_, maze, exit) = best;
+        maze.set_exits(vec![exit])
+    }
+
+    /// Scatters rocks around a random start, returns the maze with its farthest stop and the
+    /// moves needed to reach it.
+    fn layout(
+        &self,
+        rng: &mut Rng,
+        width: usize,
+        height: usize,
+    ) -> Result<(usize, Maze, (usize, usize)), MazeError> {
+        let start = (1 + rng.below(width - 2), 1 + rng.below(height - 2));
+        let map = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        let inside = x > 0 && x < width - 1 && y > 0 && y < height - 1;
+                        let rock =
+                            (x, y) != start && rng.below(100) < usize::from(self.rock_percent);
+                        MazeCell::from_bool(inside && !rock)
+                    })
+                    .collect()
+            })
+            .collect();
+        let maze = Maze::new(map, start.0, start.1)?.with_movement(Movement::Sliding);
+
+        let (moves, exit) = farthest_stop(&maze);
+        Ok((moves, maze, exit))
+    }
+}
+
+/// Cell the walker can stop on with the most moves from the start, together with the moves.
+fn farthest_stop(maze: &Maze) -> (usize, (usize, usize)) {
+    let start = (maze.start_x(), maze.start_y());
+    let mut moves = vec![vec![None; maze.width()]; maze.height()];
+    moves[start.1][start.0] = Some(0);
+    let mut queue = VecDeque::from([start]);
+    let mut farthest = (0, start);
+
+    while let Some((x, y)) = queue.pop_front() {
+        let count = moves[y][x].unwrap_or_default();
+        if count > farthest.0 {
+            farthest = (count, (x, y));
+        }
+        for (next_x, next_y) in maze.neighbours(x, y) {
+            if moves[next_y][next_x].is_none() {
+                moves[next_y][next_x] = Some(count + 1);
+                queue.push_back((next_x, next_y));
+            }
+        }
+    }
+
+    farthest
+}
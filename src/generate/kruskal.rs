@@ -0,0 +1,73 @@
+use super::{Generator, Grid};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Generator of perfect mazes using (randomized) Kruskal's algorithm.
+///
+/// Walls are removed in random order whenever they separate two unconnected regions, which
+/// results in many short dead ends spread evenly over the maze.
+#[derive(Clone, Debug)]
+pub struct Kruskal {
+    seed: u64,
+}
+
+impl Kruskal {
+    /// Creates a new [`Kruskal`] generator with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+/// Find the representative of `index` with path halving.
+fn find(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+impl Generator for Kruskal {
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError> {
+        let mut grid = Grid::new(width, height, topology)?;
+        let mut rng = Rng::new(self.seed);
+
+        // every wall between two cells, each only once
+        let index = |(x, y): (usize, usize)| y * width + x;
+        let mut walls: Vec<((usize, usize), (usize, usize))> = grid
+            .cells()
+            .into_iter()
+            .flat_map(|cell| {
+                grid.neighbours(cell)
+                    .into_iter()
+                    .filter(move |&next| index(next) > index(cell))
+                    .map(move |next| (cell, next))
+            })
+            .collect();
+        rng.shuffle(&mut walls);
+
+        // union-find over the cell indices
+        let mut parents: Vec<usize> = (0..width * height).collect();
+
+        for (a, b) in walls {
+            let root_a = find(&mut parents, index(a));
+            let root_b = find(&mut parents, index(b));
+            if root_a != root_b {
+                parents[root_a] = root_b;
+                grid.link(a, b);
+            }
+        }
+
+        // a single cell has no walls to remove
+        grid.carve((0, 0));
+
+        grid.into_maze(&mut rng)
+    }
+}
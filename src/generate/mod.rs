@@ -0,0 +1,216 @@
+mod aldous_broder;
+mod backtracker;
+mod binary_tree;
+mod eller;
+mod growing_tree;
+mod hex;
+mod hunt_and_kill;
+mod ice;
+mod kruskal;
+mod polar;
+mod prim;
+mod recursive_division;
+mod sidewinder;
+mod wilson;
+
+pub use aldous_broder::AldousBroder;
+pub use backtracker::RecursiveBacktracker;
+pub use binary_tree::BinaryTree;
+pub use eller::Eller;
+pub use growing_tree::{CellSelection, GrowingTree};
+pub use hex::HexBacktracker;
+pub use hunt_and_kill::HuntAndKill;
+pub use ice::IcePuzzle;
+pub use kruskal::Kruskal;
+pub use polar::PolarBacktracker;
+pub use prim::Prim;
+pub use recursive_division::RecursiveDivision;
+pub use sidewinder::Sidewinder;
+pub use wilson::Wilson;
+
+use crate::cell::MazeCell;
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Algorithm creating perfect mazes, mazes with exactly one route between any two cells.
+///
+/// All generators are deterministic for a given seed. They differ in the texture of the mazes
+/// they create, from long winding corridors ([`RecursiveBacktracker`]) to many short dead ends
+/// ([`Prim`]) or a strong diagonal bias ([`BinaryTree`]). None of them recurse, so large mazes
+/// don't overflow the stack.
+pub trait Generator {
+    /// Generates a perfect maze of `width` x `height` cells.
+    ///
+    /// The resulting [`Maze`] is `2 * width + 1` x `2 * height + 1` including the walls, starts
+    /// in the top left cell and has one opening in the right border.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `width` or `height` is `0`.
+    fn generate(&self, width: usize, height: usize) -> Result<Maze, MazeError> {
+        self.generate_with_topology(width, height, Topology::Bounded)
+    }
+
+    /// Generates a perfect maze like [`Generator::generate`] with the given [`Topology`].
+    ///
+    /// A [`Topology::Torus`] shares the walls of opposite edges, so the maze is only
+    /// `2 * width` x `2 * height`. Corridors may lead across the edges of sides with at least
+    /// 3 cells, and the explicit exit is the bottom right cell.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `width` or `height` is `0`, or `1` for a
+    /// [`Topology::Torus`].
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError>;
+}
+
+/// Grid of cells used while carving a perfect maze.
+///
+/// A grid of `width` x `height` cells becomes a [`Maze`] of `2 * width + 1` x `2 * height + 1`,
+/// cells are at odd coordinates and the walls between them at even ones. On a
+/// [`Topology::Torus`] the last row and column of walls are the same as the first ones.
+struct Grid {
+    width: usize,
+    height: usize,
+    topology: Topology,
+    floor: Vec<Vec<bool>>,
+}
+
+impl Grid {
+    fn new(width: usize, height: usize, topology: Topology) -> Result<Self, MazeError> {
+        let shared = match topology {
+            Topology::Bounded => 0,
+            Topology::Torus => 1,
+        };
+        let (map_width, map_height) = (2 * width + 1 - shared, 2 * height + 1 - shared);
+        if width == 0 || height == 0 {
+            return Err(MazeError::TooSmall {
+                width: map_width,
+                height: map_height,
+            });
+        }
+        Ok(Self {
+            width,
+            height,
+            topology,
+            floor: vec![vec![false; map_width]; map_height],
+        })
+    }
+
+    /// Creates a grid without any walls between its cells, except across the edges.
+    fn open(width: usize, height: usize, topology: Topology) -> Result<Self, MazeError> {
+        let mut grid = Self::new(width, height, topology)?;
+        for cell in grid.cells() {
+            for next in grid.neighbours(cell) {
+                if cell.0.abs_diff(next.0) + cell.1.abs_diff(next.1) == 1 {
+                    grid.link(cell, next);
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    /// All cells, row by row.
+    fn cells(&self) -> Vec<(usize, usize)> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .collect()
+    }
+
+    fn is_carved(&self, (x, y): (usize, usize)) -> bool {
+        self.floor[2 * y + 1][2 * x + 1]
+    }
+
+    fn carve(&mut self, (x, y): (usize, usize)) {
+        self.floor[2 * y + 1][2 * x + 1] = true;
+    }
+
+    /// Position of the wall between the neighbours `a` and `b`.
+    fn wall(a: (usize, usize), b: (usize, usize)) -> (usize, usize) {
+        // neighbours across the edges of a torus share the first wall
+        let between = |a: usize, b: usize| if a.abs_diff(b) > 1 { 0 } else { a + b + 1 };
+        (between(a.0, b.0), between(a.1, b.1))
+    }
+
+    /// Carves both cells and the wall between them, `a` and `b` must be neighbours.
+    fn link(&mut self, a: (usize, usize), b: (usize, usize)) {
+        self.carve(a);
+        self.carve(b);
+        let (x, y) = Self::wall(a, b);
+        self.floor[y][x] = true;
+    }
+
+    /// Puts the wall between the neighbours `a` and `b` back.
+    fn unlink(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let (x, y) = Self::wall(a, b);
+        self.floor[y][x] = false;
+    }
+
+    /// Neighbouring cells of `(x, y)`, in the order left, right, up, down.
+    ///
+    /// On a [`Topology::Torus`] sides of at least 3 cells wrap around, smaller ones would make
+    /// the same cell a neighbour twice.
+    fn neighbours(&self, (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+        let wrap = |size: usize| self.topology == Topology::Torus && size >= 3;
+        let mut neighbours = Vec::with_capacity(4);
+        if x > 0 {
+            neighbours.push((x - 1, y));
+        } else if wrap(self.width) {
+            neighbours.push((self.width - 1, y));
+        }
+        if x + 1 < self.width {
+            neighbours.push((x + 1, y));
+        } else if wrap(self.width) {
+            neighbours.push((0, y));
+        }
+        if y > 0 {
+            neighbours.push((x, y - 1));
+        } else if wrap(self.height) {
+            neighbours.push((x, self.height - 1));
+        }
+        if y + 1 < self.height {
+            neighbours.push((x, y + 1));
+        } else if wrap(self.height) {
+            neighbours.push((x, 0));
+        }
+        neighbours
+    }
+
+    /// Neighbouring cells of `cell` which are carved (`true`) or not carved yet (`false`).
+    fn neighbours_carved(&self, cell: (usize, usize), carved: bool) -> Vec<(usize, usize)> {
+        self.neighbours(cell)
+            .into_iter()
+            .filter(|&next| self.is_carved(next) == carved)
+            .collect()
+    }
+
+    /// Starts the maze in the top left cell and opens the right border next to a random cell.
+    ///
+    /// A [`Topology::Torus`] has no border, its exit is the bottom right cell instead.
+    fn into_maze(mut self, rng: &mut Rng) -> Result<Maze, MazeError> {
+        let exits = match self.topology {
+            Topology::Bounded => {
+                let exit_y = 2 * rng.below(self.height) + 1;
+                self.floor[exit_y][2 * self.width] = true;
+                Vec::new()
+            }
+            Topology::Torus => vec![(2 * self.width - 1, 2 * self.height - 1)],
+        };
+
+        let map = self
+            .floor
+            .into_iter()
+            .map(|row| row.into_iter().map(MazeCell::from_bool).collect())
+            .collect();
+        Maze::new(map, 1, 1)?
+            .with_topology(self.topology)
+            .set_exits(exits)
+    }
+}
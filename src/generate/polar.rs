@@ -0,0 +1,58 @@
+use crate::error::MazeError;
+use crate::polar::PolarMaze;
+use crate::rng::Rng;
+
+/// Generator of perfect [`PolarMaze`]s using the recursive backtracker algorithm.
+#[derive(Clone, Debug)]
+pub struct PolarBacktracker {
+    seed: u64,
+}
+
+impl PolarBacktracker {
+    /// Creates a new [`PolarBacktracker`] with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Generates a perfect circular maze of `rings` rings including the centre cell.
+    ///
+    /// The exit is opened at a random cell of the outer ring.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `rings` is smaller than `2`.
+    pub fn generate(&self, rings: usize) -> Result<PolarMaze, MazeError> {
+        let mut maze = PolarMaze::closed(rings)?;
+        let mut rng = Rng::new(self.seed);
+
+        let mut visited: Vec<Vec<bool>> = maze
+            .ring_sizes
+            .iter()
+            .map(|&size| vec![false; size])
+            .collect();
+        let start = (0, 0);
+        visited[0][0] = true;
+        let mut stack = vec![start];
+
+        while let Some(&cell) = stack.last() {
+            let unvisited: Vec<(usize, usize)> = maze
+                .neighbours(cell)
+                .into_iter()
+                .filter(|&(x, ring)| !visited[ring][x])
+                .collect();
+            match rng.choose(&unvisited) {
+                Some(&next) => {
+                    visited[next.1][next.0] = true;
+                    maze.link(cell, next);
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+
+        maze.exit = rng.below(maze.ring_sizes[rings - 1]);
+        Ok(maze)
+    }
+}
@@ -0,0 +1,52 @@
+use super::{Generator, Grid};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Generator of perfect mazes using (randomized) Prim's algorithm.
+///
+/// The maze grows from a random cell by connecting random frontier cells, which results in
+/// many short dead ends branching off in all directions.
+#[derive(Clone, Debug)]
+pub struct Prim {
+    seed: u64,
+}
+
+impl Prim {
+    /// Creates a new [`Prim`] generator with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Generator for Prim {
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError> {
+        let mut grid = Grid::new(width, height, topology)?;
+        let mut rng = Rng::new(self.seed);
+
+        let start = (rng.below(width), rng.below(height));
+        grid.carve(start);
+        let mut frontier = grid.neighbours(start);
+
+        while !frontier.is_empty() {
+            let cell = frontier.swap_remove(rng.below(frontier.len()));
+            if grid.is_carved(cell) {
+                // added to the frontier more than once
+                continue;
+            }
+
+            if let Some(&next) = rng.choose(&grid.neighbours_carved(cell, true)) {
+                grid.link(cell, next);
+            }
+            frontier.extend(grid.neighbours_carved(cell, false));
+        }
+
+        grid.into_maze(&mut rng)
+    }
+}
@@ -0,0 +1,108 @@
+use super::{Generator, Grid};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Generator of perfect mazes using the Recursive Division algorithm.
+///
+/// Starts with an empty field and divides it with walls, each with a single gap, until the
+/// chambers are one cell wide. Creates long straight walls and a visible box structure.
+#[derive(Clone, Debug)]
+pub struct RecursiveDivision {
+    seed: u64,
+}
+
+impl RecursiveDivision {
+    /// Creates a new [`RecursiveDivision`] generator with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+/// Rectangle of cells still to be divided.
+struct Chamber {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Generator for RecursiveDivision {
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError> {
+        let mut grid = Grid::open(width, height, topology)?;
+        let mut rng = Rng::new(self.seed);
+
+        let mut chambers = vec![Chamber {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        }];
+
+        while let Some(Chamber {
+            x,
+            y,
+            width,
+            height,
+        }) = chambers.pop()
+        {
+            if width < 2 && height < 2 {
+                continue;
+            }
+
+            // divide across the longer side, randomly if square
+            let horizontal = height > width || (height == width && rng.coin());
+
+            if horizontal {
+                // wall below row `y + split`, gap in column `x + gap`
+                let split = rng.below(height - 1);
+                let gap = rng.below(width);
+                for column in (x..x + width).filter(|&column| column != x + gap) {
+                    grid.unlink((column, y + split), (column, y + split + 1));
+                }
+                chambers.push(Chamber {
+                    x,
+                    y,
+                    width,
+                    height: split + 1,
+                });
+                chambers.push(Chamber {
+                    x,
+                    y: y + split + 1,
+                    width,
+                    height: height - split - 1,
+                });
+            } else {
+                // wall right of column `x + split`, gap in row `y + gap`
+                let split = rng.below(width - 1);
+                let gap = rng.below(height);
+                for row in (y..y + height).filter(|&row| row != y + gap) {
+                    grid.unlink((x + split, row), (x + split + 1, row));
+                }
+                chambers.push(Chamber {
+                    x,
+                    y,
+                    width: split + 1,
+                    height,
+                });
+                chambers.push(Chamber {
+                    x: x + split + 1,
+                    y,
+                    width: width - split - 1,
+                    height,
+                });
+            }
+        }
+
+        // a single cell has no neighbours to link with
+        grid.carve((0, 0));
+
+        grid.into_maze(&mut rng)
+    }
+}
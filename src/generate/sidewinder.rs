@@ -0,0 +1,56 @@
+use super::{Generator, Grid};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Generator of perfect mazes using the Sidewinder algorithm.
+///
+/// Each row is split into random runs of cells, every run connects upwards once. The top row is
+/// always one long corridor.
+#[derive(Clone, Debug)]
+pub struct Sidewinder {
+    seed: u64,
+}
+
+impl Sidewinder {
+    /// Creates a new [`Sidewinder`] generator with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Generator for Sidewinder {
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError> {
+        let mut grid = Grid::new(width, height, topology)?;
+        let mut rng = Rng::new(self.seed);
+
+        for y in 0..height {
+            let mut run_start = 0;
+            for x in 0..width {
+                grid.carve((x, y));
+                let at_east_end = x + 1 == width;
+
+                if y == 0 {
+                    if !at_east_end {
+                        grid.link((x, y), (x + 1, y));
+                    }
+                } else if at_east_end || rng.coin() {
+                    // close the run and connect it upwards
+                    let up_x = run_start + rng.below(x - run_start + 1);
+                    grid.link((up_x, y), (up_x, y - 1));
+                    run_start = x + 1;
+                } else {
+                    grid.link((x, y), (x + 1, y));
+                }
+            }
+        }
+
+        grid.into_maze(&mut rng)
+    }
+}
@@ -0,0 +1,69 @@
+use super::{Generator, Grid};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::rng::Rng;
+use crate::shape::Topology;
+
+/// Generator of perfect mazes using Wilson's algorithm.
+///
+/// Loop-erased random walks are added until every cell is part of the maze. The result is a
+/// uniform spanning tree, an unbiased sample of all possible mazes.
+#[derive(Clone, Debug)]
+pub struct Wilson {
+    seed: u64,
+}
+
+impl Wilson {
+    /// Creates a new [`Wilson`] generator with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Generator for Wilson {
+    fn generate_with_topology(
+        &self,
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Result<Maze, MazeError> {
+        let mut grid = Grid::new(width, height, topology)?;
+        let mut rng = Rng::new(self.seed);
+
+        let mut cells = grid.cells();
+        rng.shuffle(&mut cells);
+        if let Some(&first) = cells.first() {
+            grid.carve(first);
+        }
+
+        // direction the walk last left each cell in, overwriting it erases loops
+        let mut next_cell: Vec<Vec<Option<(usize, usize)>>> = vec![vec![None; width]; height];
+
+        for &walk_start in &cells {
+            if grid.is_carved(walk_start) {
+                continue;
+            }
+
+            let mut cell = walk_start;
+            while !grid.is_carved(cell) {
+                let neighbours = grid.neighbours(cell);
+                let next = neighbours[rng.below(neighbours.len())];
+                next_cell[cell.1][cell.0] = Some(next);
+                cell = next;
+            }
+
+            // add the loop-erased walk to the maze
+            let mut cell = walk_start;
+            while let Some(next) = next_cell[cell.1][cell.0] {
+                let reached_maze = grid.is_carved(next);
+                grid.link(cell, next);
+                if reached_maze {
+                    break;
+                }
+                cell = next;
+            }
+        }
+
+        grid.into_maze(&mut rng)
+    }
+}
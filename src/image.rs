@@ -0,0 +1,233 @@
+use crate::cell::MazeCell;
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::png;
+
+/// Settings of [`Maze::from_image`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageImport {
+    threshold: u8,
+    cell_size: usize,
+    start: Option<(usize, usize)>,
+}
+
+impl Default for ImageImport {
+    fn default() -> Self {
+        Self {
+            threshold: 128,
+            cell_size: 1,
+            start: None,
+        }
+    }
+}
+
+impl ImageImport {
+    /// Sets the brightness from `0` to `255` below which a cell is a wall.
+    pub fn with_threshold(mut self, threshold: u8) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the width and height of a cell in pixels.
+    pub fn with_cell_size(mut self, cell_size: usize) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Sets the starting position in cells, for images without a green cell like grey ones.
+    pub fn with_start(mut self, start_x: usize, start_y: usize) -> Self {
+        self.start = Some((start_x, start_y));
+        self
+    }
+}
+
+impl Maze {
+    /// Reads a [`Maze`] from a PNG, PPM or PGM image.
+    ///
+    /// Every block of `cell_size` x `cell_size` pixels is one cell, pixels left over at the right
+    /// and bottom edges are ignored. A cell darker than the threshold on average is a wall,
+    /// otherwise floor. A clearly green cell is the start and clearly red cells are exits, as
+    /// drawn by [`Maze::to_png`]. A start set with [`ImageImport::with_start`] takes precedence,
+    /// without red cells the exits are on the border.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the image is damaged or in an unsupported format,
+    /// if `cell_size` is `0`, or if the resulting maze is too small, has no valid start or
+    /// several green cells.
+    pub fn from_image(image: &[u8], import: &ImageImport) -> Result<Maze, MazeError> {
+        let cell_size = import.cell_size;
+        if cell_size == 0 {
+            return Err(MazeError::InvalidCellSize { size: cell_size });
+        }
+        let image = Image::decode(image)?;
+
+        let mut start = None;
+        let mut exits = Vec::new();
+        let mut map = Vec::with_capacity(image.height / cell_size);
+        for y in 0..image.height / cell_size {
+            let mut row = Vec::with_capacity(image.width / cell_size);
+            for x in 0..image.width / cell_size {
+                let colour = image.average(x * cell_size, y * cell_size, cell_size);
+                let cell = if dominates(colour, 1) {
+                    if start.replace((x, y)).is_some() {
+                        return Err(MazeError::MultipleStarts { x, y });
+                    }
+                    MazeCell::from_bool(true)
+                } else if dominates(colour, 0) {
+                    exits.push((x, y));
+                    MazeCell::from_bool(true)
+                } else {
+                    MazeCell::from_bool(brightness(colour) >= import.threshold)
+                };
+                row.push(cell);
+            }
+            map.push(row);
+        }
+
+        let (start_x, start_y) = import.start.or(start).ok_or(MazeError::MissingStart)?;
+        Maze::new(map, start_x, start_y)?.set_exits(exits)
+    }
+}
+
+/// Decoded image of RGB pixels, row by row.
+pub(crate) struct Image {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) pixels: Vec<[u8; 3]>,
+}
+
+impl Image {
+    /// Decodes a PNG image or a PPM or PGM image in the plain or raw variant.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `image` is damaged or in an unsupported format.
+    fn decode(image: &[u8]) -> Result<Image, MazeError> {
+        match image {
+            [0x89, b'P', b'N', b'G', ..] => png::decode(image),
+            [b'P', b'2' | b'3' | b'5' | b'6', ..] => decode_netpbm(image),
+            _ => Err(MazeError::InvalidImage {
+                reason: "unknown image format",
+            }),
+        }
+    }
+
+    /// Average colour of the `size` x `size` pixels with the top left one at `x`, `y`.
+    fn average(&self, x: usize, y: usize, size: usize) -> [u8; 3] {
+        let mut sum = [0usize; 3];
+        for row in y..y + size {
+            for pixel in &self.pixels[row * self.width + x..row * self.width + x + size] {
+                for (sum, &value) in sum.iter_mut().zip(pixel) {
+                    *sum += value as usize;
+                }
+            }
+        }
+        sum.map(|sum| (sum / (size * size)) as u8)
+    }
+}
+
+/// Decodes a PGM (`P2`, `P5`) or PPM (`P3`, `P6`) image.
+fn decode_netpbm(image: &[u8]) -> Result<Image, MazeError> {
+    let invalid = |reason| MazeError::InvalidImage { reason };
+    let (plain, channels) = match image[1] {
+        b'2' => (true, 1),
+        b'3' => (true, 3),
+        b'5' => (false, 1),
+        _ => (false, 3),
+    };
+
+    let mut position = 2;
+    // next number in text, skipping whitespace and comments
+    let number = |position: &mut usize| -> Result<usize, MazeError> {
+        loop {
+            match image.get(*position) {
+                Some(byte) if byte.is_ascii_whitespace() => *position += 1,
+                Some(b'#') => {
+                    while image.get(*position).is_some_and(|&byte| byte != b'\n') {
+                        *position += 1;
+                    }
+                }
+                Some(byte) if byte.is_ascii_digit() => break,
+                Some(_) => return Err(invalid("unexpected character in image header")),
+                None => return Err(invalid("image ends early")),
+            }
+        }
+        let mut value: usize = 0;
+        while let Some(&byte) = image.get(*position).filter(|byte| byte.is_ascii_digit()) {
+            value = value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add((byte - b'0') as usize))
+                .ok_or(invalid("number too large in image"))?;
+            *position += 1;
+        }
+        Ok(value)
+    };
+
+    let width = number(&mut position)?;
+    let height = number(&mut position)?;
+    let max = number(&mut position)?;
+    if width == 0 || height == 0 {
+        return Err(invalid("image without pixels"));
+    }
+    if !(1..=65535).contains(&max) {
+        return Err(invalid("maximum value of image out of range"));
+    }
+
+    let count = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(channels))
+        .ok_or(invalid("image too large"))?;
+    let mut samples = Vec::with_capacity(count.min(image.len()));
+    if plain {
+        for _ in 0..count {
+            samples.push(number(&mut position)?);
+        }
+    } else {
+        // a single whitespace character separates the header from the samples
+        let bytes = if max > 255 { 2 } else { 1 };
+        let data = count
+            .checked_mul(bytes)
+            .and_then(|length| length.checked_add(position + 1))
+            .and_then(|end| image.get(position + 1..end))
+            .ok_or(invalid("image ends early"))?;
+        samples.extend(data.chunks_exact(bytes).map(|sample| match sample {
+            [high, low] => (*high as usize) << 8 | *low as usize,
+            _ => sample[0] as usize,
+        }));
+    }
+    if samples.iter().any(|&sample| sample > max) {
+        return Err(invalid("sample above the maximum value of image"));
+    }
+
+    let pixels = samples
+        .chunks_exact(channels)
+        .map(|pixel| {
+            let scale = |sample: usize| (sample * 255 / max) as u8;
+            match pixel {
+                [red, green, blue] => [scale(*red), scale(*green), scale(*blue)],
+                _ => [scale(pixel[0]); 3],
+            }
+        })
+        .collect();
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Perceived brightness of `colour` from `0` to `255`.
+fn brightness([red, green, blue]: [u8; 3]) -> u8 {
+    ((299 * red as usize + 587 * green as usize + 114 * blue as usize) / 1000) as u8
+}
+
+/// Whether the channel `index` of `colour` is clearly stronger than both others.
+fn dominates(colour: [u8; 3], index: usize) -> bool {
+    let value = colour[index] as usize;
+    colour
+        .iter()
+        .enumerate()
+        .filter(|&(other, _)| other != index)
+        .all(|(_, &other)| value > 2 * other as usize && value >= other as usize + 64)
+}
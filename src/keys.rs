@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use crate::cell::{FloorType, MazeCell};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::search;
+use crate::solve::Path;
+
+/// Route found by [`Maze::find_path_with_keys`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyPath {
+    /// Cells from the start to the exit.
+    pub path: Path,
+    /// Labels of the keys in the order they were picked up.
+    pub keys: Vec<char>,
+    /// Total [`FloorType::cost`] of the route.
+    pub cost: usize,
+}
+
+/// Bit of the key `label` in a set of keys, `0` for labels other than `'a'` to `'z'`, which
+/// never open.
+pub(crate) fn bit(label: char) -> u32 {
+    if label.is_ascii_lowercase() {
+        1 << (label as u32 - 'a' as u32)
+    } else {
+        0
+    }
+}
+
+impl Maze {
+    /// Keys picked up when stepping onto the cell at `x`, `y`.
+    fn key_at(&self, x: usize, y: usize) -> u32 {
+        match &self.map[y][x] {
+            MazeCell::Floor(FloorType::Key(label)) => bit(*label),
+            _ => 0,
+        }
+    }
+
+    /// Finds the cheapest route from the start to an exit that picks up keys to open doors.
+    ///
+    /// A [`FloorType::Door`] is passable once its [`FloorType::Key`] was visited, so the same
+    /// cell may be passed again with more keys. The search runs over pairs of position and keys
+    /// held. Returns `None` if no exit can be reached.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the starting position is out of bounds or the
+    /// maze has no border and no explicit exits.
+    pub fn find_path_with_keys(&self) -> Result<Option<KeyPath>, MazeError> {
+        self.validate_start_in_bounds()?;
+        self.validate_exits()?;
+
+        let start = (self.start_x, self.start_y);
+        let neighbours = |((x, y), keys): ((usize, usize), u32)| {
+            self.neighbours_holding(x, y, keys).map(move |next| {
+                let cost = self.move_cost((x, y), next);
+                ((next, keys | self.key_at(next.0, next.1)), cost)
+            })
+        };
+        let (states, stats) = search::cheapest(
+            (start, self.key_at(start.0, start.1)),
+            neighbours,
+            |((x, y), _)| self.is_exit(x, y),
+            |_| 0.0,
+            HashMap::default(),
+        );
+
+        Ok(states.zip(stats.path_cost).map(|(states, cost)| {
+            let mut keys = Vec::new();
+            for &((x, y), _) in &states {
+                if let MazeCell::Floor(FloorType::Key(label)) = self.map[y][x] {
+                    if !keys.contains(&label) {
+                        keys.push(label);
+                    }
+                }
+            }
+            KeyPath {
+                path: states.into_iter().map(|(position, _)| position).collect(),
+                keys,
+                cost,
+            }
+        }))
+    }
+}
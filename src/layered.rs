@@ -0,0 +1,386 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cell::{FloorType, MazeCell};
+use crate::error::MazeError;
+use crate::maze::{Markers, Maze};
+use crate::movement::Movement;
+use crate::render::RenderStyle;
+use crate::search;
+use crate::shape::{Shape, Topology};
+use crate::solve::SolveAlgorithm;
+
+/// Route through a [`LayeredMaze`] as coordinates `(x, y, z)`, starting with the start position.
+pub type LayeredPath = Vec<(usize, usize, usize)>;
+
+/// Maze of several levels of the same size stacked on top of each other.
+///
+/// Level `z` is a grid like a [`Maze`], [`FloorType::Stairs`] connect a cell to the same
+/// position on the levels directly above (`z - 1`) and below (`z + 1`) if they have stairs
+/// too. Without explicit exits every floor cell on the border of any level is an exit.
+#[derive(Clone, Debug)]
+pub struct LayeredMaze {
+    /// One grid per level, their own start is not used
+    pub(crate) levels: Vec<Maze>,
+    pub(crate) start: (usize, usize, usize),
+    /// Explicit exits, the borders are used if empty
+    pub(crate) exits: Vec<(usize, usize, usize)>,
+}
+
+impl LayeredMaze {
+    /// Creates a new [`LayeredMaze`] from levels indexed as `levels[z][y][x]`.
+    ///
+    /// Smaller levels and shorter rows are filled up with walls.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the levels are smaller than 3x3, the starting
+    /// position is out of bounds or on a wall, or a [`FloorType::Portal`] has no single partner
+    /// on its level. Errors at a position are wrapped in [`MazeError::OnLevel`].
+    pub fn new(
+        mut levels: Vec<Vec<Vec<MazeCell>>>,
+        start_x: usize,
+        start_y: usize,
+        start_z: usize,
+    ) -> Result<Self, MazeError> {
+        let height = levels.iter().map(|level| level.len()).max();
+        let width = levels.iter().flatten().map(|row| row.len()).max();
+        let (width, height) = (width.unwrap_or_default(), height.unwrap_or_default());
+
+        if height < 3 || width < 3 {
+            return Err(MazeError::TooSmall { width, height });
+        }
+
+        // make sure all levels and rows are the same size
+        let levels = levels
+            .iter_mut()
+            .enumerate()
+            .map(|(z, map)| {
+                map.resize(height, Vec::new());
+                for row in map.iter_mut() {
+                    row.resize(width, MazeCell::default());
+                }
+                Ok(Maze {
+                    portals: Maze::pair_portals(map).map_err(on_level(z))?,
+                    map: std::mem::take(map),
+                    width,
+                    height,
+                    start_x: 0,
+                    start_y: 0,
+                    exits: Vec::new(),
+                    movement: Movement::default(),
+                    shape: Shape::Square,
+                    topology: Topology::Bounded,
+                    marked: BTreeSet::new(),
+                })
+            })
+            .collect::<Result<_, MazeError>>()?;
+
+        let maze = Self {
+            levels,
+            start: (start_x, start_y, start_z),
+            exits: Vec::new(),
+        };
+        let error = match maze.cell((start_x, start_y, start_z)) {
+            Some(MazeCell::Floor(_)) => return Ok(maze),
+            Some(MazeCell::Wall) => MazeError::StartOnWall {
+                x: start_x,
+                y: start_y,
+            },
+            None => MazeError::StartOutOfBounds {
+                x: start_x,
+                y: start_y,
+            },
+        };
+        Err(on_level(start_z)(error))
+    }
+
+    fn cell(&self, (x, y, z): (usize, usize, usize)) -> Option<&MazeCell> {
+        self.levels
+            .get(z)
+            .and_then(|level| level.map.get(y))
+            .and_then(|row| row.get(x))
+    }
+
+    /// Returns the number of columns of each level.
+    pub fn width(&self) -> usize {
+        self.levels[0].width
+    }
+
+    /// Returns the number of rows of each level.
+    pub fn height(&self) -> usize {
+        self.levels[0].height
+    }
+
+    /// Returns the number of levels.
+    pub fn depth(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns the starting position as `(x, y, z)`.
+    pub fn start(&self) -> (usize, usize, usize) {
+        self.start
+    }
+
+    /// Returns the explicit exits, empty if every floor cell on a border is an exit.
+    pub fn exits(&self) -> &[(usize, usize, usize)] {
+        &self.exits
+    }
+
+    /// Sets the explicit exits of this [`LayeredMaze`], an empty list makes the borders the
+    /// exit again.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if an exit is out of bounds or on a wall, wrapped in
+    /// [`MazeError::OnLevel`] with the level of the exit.
+    pub fn set_exits(mut self, exits: Vec<(usize, usize, usize)>) -> Result<Self, MazeError> {
+        for &(x, y, z) in &exits {
+            let error = match self.cell((x, y, z)) {
+                None => MazeError::ExitOutOfBounds { x, y },
+                Some(MazeCell::Wall) => MazeError::ExitOnWall { x, y },
+                Some(MazeCell::Floor(_)) => continue,
+            };
+            return Err(on_level(z)(error));
+        }
+        self.exits = exits;
+        Ok(self)
+    }
+
+    /// Sets the [`Movement`] used on every level.
+    pub fn with_movement(mut self, movement: Movement) -> Self {
+        for level in &mut self.levels {
+            level.movement = movement;
+        }
+        self
+    }
+
+    fn is_exit(&self, (x, y, z): (usize, usize, usize)) -> bool {
+        if self.exits.is_empty() {
+            self.levels[z].is_border(x, y)
+        } else {
+            self.exits.contains(&(x, y, z))
+        }
+    }
+
+    /// Positions reachable in one step, first on the same level, then up and down the stairs.
+    fn neighbours(&self, (x, y, z): (usize, usize, usize)) -> Vec<(usize, usize, usize)> {
+        let mut neighbours: Vec<_> = self.levels[z]
+            .neighbours(x, y)
+            .map(|(x, y)| (x, y, z))
+            .collect();
+        let stairs = MazeCell::Floor(FloorType::Stairs);
+        if self.cell((x, y, z)) == Some(&stairs) {
+            neighbours.extend(
+                [(x, y, z.wrapping_sub(1)), (x, y, z + 1)]
+                    .into_iter()
+                    .filter(|&next| self.cell(next) == Some(&stairs)),
+            );
+        }
+        neighbours
+    }
+
+    /// Finds a route from the start to an exit, possibly over several levels.
+    ///
+    /// Returns the route, or `None` if there is no solution.
+    pub fn find_path_with(
+        &self,
+        algorithm: SolveAlgorithm,
+    ) -> Result<Option<LayeredPath>, MazeError> {
+        let is_exit = |position| self.is_exit(position);
+        let neighbours = |position| self.neighbours(position);
+        Ok(match algorithm {
+            SolveAlgorithm::DepthFirst => {
+                search::depth_first(self.start, neighbours, is_exit, HashMap::default())
+            }
+            SolveAlgorithm::BreadthFirst => {
+                search::breadth_first(self.start, neighbours, is_exit, HashMap::default())
+            }
+            SolveAlgorithm::Dijkstra => self.find_cheapest_path()?.map(|(path, _)| path),
+        })
+    }
+
+    /// Finds a route with the default [`SolveAlgorithm`].
+    pub fn find_path(&self) -> Result<Option<LayeredPath>, MazeError> {
+        self.find_path_with(SolveAlgorithm::default())
+    }
+
+    /// Finds the route with the lowest total [`FloorType::cost`] from the start to an exit.
+    ///
+    /// Returns the route together with its cost, or `None` if there is no solution.
+    pub fn find_cheapest_path(&self) -> Result<Option<(LayeredPath, usize)>, MazeError> {
+        let neighbours = |position| {
+            self.neighbours(position).into_iter().map(move |(x, y, z)| {
+                let cost = if z == position.2 {
+                    self.levels[z].move_cost((position.0, position.1), (x, y))
+                } else {
+                    self.levels[z].step_cost(x, y)
+                };
+                ((x, y, z), cost)
+            })
+        };
+        let (path, stats) = search::cheapest(
+            self.start,
+            neighbours,
+            |position| self.is_exit(position),
+            |_| 0.0,
+            HashMap::default(),
+        );
+        Ok(path.zip(stats.path_cost))
+    }
+
+    /// Finds a route like [`LayeredMaze::find_path_with`] and marks it on its levels.
+    pub fn solve_with(&mut self, algorithm: SolveAlgorithm) -> Result<bool, MazeError> {
+        match self.find_path_with(algorithm)? {
+            Some(path) => {
+                for &(x, y, z) in &path {
+                    self.levels[z].mark_path(&[(x, y)])?;
+                }
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Solves `self` with the default [`SolveAlgorithm`] and marks the route found.
+    pub fn solve(&mut self) -> Result<bool, MazeError> {
+        self.solve_with(SolveAlgorithm::default())
+    }
+
+    /// Renders level `z` in the format of [`Maze`]'s `Display`, `None` if there is no such
+    /// level.
+    pub fn level_to_string(&self, z: usize) -> Option<String> {
+        self.render_level(z, RenderStyle::Emoji)
+    }
+
+    /// Renders all levels from the top in the given [`RenderStyle`], separated by blank lines.
+    ///
+    /// The marker lines of [`RenderStyle::Ascii`] follow the last level, with positions as
+    /// `X,Y,Z`.
+    pub fn to_string_with(&self, style: RenderStyle) -> String {
+        let levels: Vec<String> = (0..self.depth())
+            .filter_map(|z| self.render_level(z, style))
+            .collect();
+        let mut s = levels.join("\n");
+        if style == RenderStyle::Ascii {
+            let mut markers = Markers::default();
+            for (z, level) in self.levels.iter().enumerate() {
+                let on_level = level.markers(self.start_on(z), &self.exits_on(z));
+                let with_level = |[x, y]: [usize; 2]| [x, y, z];
+                markers.start = markers.start.or(on_level.start.map(with_level));
+                markers
+                    .exits
+                    .extend(on_level.exits.into_iter().map(with_level));
+                markers
+                    .path
+                    .extend(on_level.path.into_iter().map(with_level));
+            }
+            s.push_str(&markers.write());
+        }
+        s
+    }
+
+    /// Renders level `z` in `style`, `None` if there is no such level.
+    fn render_level(&self, z: usize, style: RenderStyle) -> Option<String> {
+        let level = self.levels.get(z)?;
+        Some(level.render(self.start_on(z), &self.exits_on(z), style))
+    }
+
+    /// Start on level `z` as `(x, y)`, `None` if it is on another level.
+    fn start_on(&self, z: usize) -> Option<(usize, usize)> {
+        (self.start.2 == z).then_some((self.start.0, self.start.1))
+    }
+
+    /// Exits on level `z` as `(x, y)`.
+    fn exits_on(&self, z: usize) -> Vec<(usize, usize)> {
+        self.exits
+            .iter()
+            .filter(|exit| exit.2 == z)
+            .map(|&(x, y, _)| (x, y))
+            .collect()
+    }
+}
+
+impl fmt::Display for LayeredMaze {
+    /// Renders all levels from the top, separated by blank lines.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_with(RenderStyle::Emoji))
+    }
+}
+
+impl FromStr for LayeredMaze {
+    type Err = MazeError;
+
+    /// Parses levels in the format of [`Maze::new_from_str_array`] separated by blank lines,
+    /// starting with the top level.
+    ///
+    /// The starting position is taken from the single `'S'` marker of all levels. Marker lines
+    /// follow the last level, with positions as `X,Y,Z`. Lines of
+    /// [`MazeError::UnknownCharacter`] and [`MazeError::InvalidMarkers`] count from the start of
+    /// `s`, errors at a position are wrapped in [`MazeError::OnLevel`] like those of
+    /// [`LayeredMaze::new`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().collect();
+        let rows = Markers::<3>::start_of(&lines);
+        let (lines, markers) = lines.split_at(rows);
+        let markers = Markers::<3>::parse(markers, rows)?;
+        let mut levels = Vec::new();
+        let mut start = None;
+        let mut exits = Vec::new();
+
+        // each level with the number of lines before it
+        let mut offset = 0;
+        let groups = lines.split(|line| line.is_empty()).map(|rows| {
+            let first = offset;
+            offset += rows.len() + 1;
+            (first, rows)
+        });
+        for (z, (first, rows)) in groups.filter(|(_, rows)| !rows.is_empty()).enumerate() {
+            let parsed = Maze::parse_rows(rows).map_err(|error| match error {
+                MazeError::UnknownCharacter {
+                    character,
+                    line,
+                    column,
+                } => MazeError::UnknownCharacter {
+                    character,
+                    line: first + line,
+                    column,
+                },
+                error => on_level(z)(error),
+            })?;
+            if let Some((x, y)) = parsed.start {
+                if start.replace((x, y, z)).is_some() {
+                    return Err(on_level(z)(MazeError::MultipleStarts { x, y }));
+                }
+            }
+            exits.extend(parsed.exits.into_iter().map(|(x, y)| (x, y, z)));
+            levels.push(parsed.map);
+        }
+
+        if let Some([x, y, z]) = markers.start {
+            if start.replace((x, y, z)).is_some() {
+                return Err(on_level(z)(MazeError::MultipleStarts { x, y }));
+            }
+        }
+        exits.extend(markers.exits.into_iter().map(|[x, y, z]| (x, y, z)));
+
+        let (start_x, start_y, start_z) = start.ok_or(MazeError::MissingStart)?;
+        let mut maze = LayeredMaze::new(levels, start_x, start_y, start_z)?.set_exits(exits)?;
+        for [x, y, z] in markers.path {
+            match maze.levels.get_mut(z) {
+                Some(level) => level.mark_path(&[(x, y)]).map_err(on_level(z))?,
+                None => return Err(on_level(z)(MazeError::PathOutOfBounds { x, y })),
+            }
+        }
+        Ok(maze)
+    }
+}
+
+/// Wraps an error at a position on level `z` in [`MazeError::OnLevel`].
+fn on_level(z: usize) -> impl Fn(MazeError) -> MazeError {
+    move |error| MazeError::OnLevel {
+        z,
+        error: Box::new(error),
+    }
+}
@@ -0,0 +1,66 @@
+//! Create, display and solve rectangular mazes.
+//!
+//! A [`Maze`] is a grid of [`MazeCell`]s with a starting position. Exits are either given
+//! explicitly or every floor cell on the border. Mazes implementing [`Solvable`] can search a
+//! route from the start to an exit, either returning it as a [`Path`] or marking it in the maze
+//! itself. Circular mazes are available as [`PolarMaze`], mazes of several levels connected by
+//! stairs as [`LayeredMaze`].
+//!
+//! Random perfect mazes can be created with the seeded generators implementing [`Generator`],
+//! for example the [`RecursiveBacktracker`].
+//!
+//! Cells are square by default, [`Shape::Hex`] turns the same grid into hexagons in offset
+//! coordinates. [`HexBacktracker`] generates hex mazes and [`Maze::to_svg_with`] draws either
+//! shape in an [`SvgStyle`], together with a solution. [`Maze::to_png`] and [`Maze::from_image`]
+//! write and read raster images.
+//! With [`Topology::Torus`] a maze wraps around its edges and needs explicit exits.
+//!
+//! ```
+//! use maze::{Maze, Solvable, SolveAlgorithm};
+//!
+//! let maze = Maze::new_from_str("XXXXX\nX   X\nX X  \nXXXXX", 1, 1)?;
+//!
+//! let path = maze
+//!     .find_path_with(SolveAlgorithm::BreadthFirst)?
+//!     .expect("maze has an exit");
+//! assert_eq!(path, vec![(1, 1), (2, 1), (3, 1), (3, 2), (4, 2)]);
+//!
+//! println!("{}", maze.with_path(&path)?);
+//! # Ok::<(), maze::MazeError>(())
+//! ```
+
+mod cell;
+mod error;
+mod generate;
+mod image;
+mod keys;
+mod layered;
+mod maze;
+mod movement;
+mod png;
+mod polar;
+mod render;
+mod rng;
+mod search;
+mod shape;
+mod solve;
+mod svg;
+mod zlib;
+
+pub use crate::cell::{AsChar, FloorType, MazeCell};
+pub use crate::error::MazeError;
+pub use crate::generate::{
+    AldousBroder, BinaryTree, CellSelection, Eller, Generator, GrowingTree, HexBacktracker,
+    HuntAndKill, IcePuzzle, Kruskal, PolarBacktracker, Prim, RecursiveBacktracker,
+    RecursiveDivision, Sidewinder, Wilson,
+};
+pub use crate::image::ImageImport;
+pub use crate::keys::KeyPath;
+pub use crate::layered::{LayeredMaze, LayeredPath};
+pub use crate::maze::Maze;
+pub use crate::movement::{CornerCutting, Direction, Movement};
+pub use crate::polar::PolarMaze;
+pub use crate::render::RenderStyle;
+pub use crate::shape::{Shape, Topology};
+pub use crate::solve::{Heuristic, Path, SearchStats, Solvable, SolveAlgorithm};
+pub use crate::svg::SvgStyle;
@@ -1,351 +1,442 @@
-mod maze {
-    use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+use maze::{
+    CornerCutting, Heuristic, ImageImport, Maze, Movement, RenderStyle, Shape, Solvable,
+    SolveAlgorithm, SvgStyle, Topology,
+};
+
+/// Exit code if a solution was found.
+const EXIT_SOLVED: u8 = 0;
+/// Exit code if the maze has no solution.
+const EXIT_NO_SOLUTION: u8 = 1;
+/// Exit code for invalid arguments or maze data.
+const EXIT_INVALID_INPUT: u8 = 2;
+/// Exit code if the output could not be written.
+const EXIT_OUTPUT_ERROR: u8 = 3;
+
+const USAGE: &str = "\
+Usage: maze [OPTIONS] [FILE]
+
+Reads a maze in the `X`/space format, or as an image with --input, from FILE (or stdin if
+FILE is missing or `-`), solves it and prints the solution. The maze may mark the start with
+`S` and exits with `E`, terrain is written as `=` (road), `%` (mud) and `~` (water) and
+portals as pairs of the same lowercase letter. Uppercase letters are doors, opened by the
+lowercase letter as their key. `<`, `>`, `^` and `v` can only be left in their direction,
+conveyors `4`, `6`, `8` and `2` move the walker on like the arrows on a number pad, `*` is
+ice and `.` a path, as printed by --style ascii. Lines like `@start X,Y`, `@exits X,Y ...`
+and `@path X,Y ...` after the rows mark cells that keep their own character.
+
+Options:
+  -s, --start <X,Y>          Starting position [default: the `S` in the maze]
+  -a, --solver <SOLVER>      dfs, bfs, dijkstra, astar or keys [default: dfs], keys picks
+                             up keys to open doors and prints their order to stderr
+  -g, --goal <X,Y>           Goal cell for astar, can be given multiple times
+                             [default: the `E`s in the maze]
+      --heuristic <NAME>     Estimate for astar: manhattan, chebyshev, euclidean or zero
+                             [default: manhattan]
+  -m, --movement <MOVES>     orthogonal, diagonal or sliding [default: orthogonal], sliding
+                             moves on until blocked, use bfs for the fewest moves
+      --corners <RULE>       Diagonal steps past walls with -m diagonal: never, one-wall
+                             or always [default: never]
+      --shape <SHAPE>        square or hex, hex rows are in odd-r offset coordinates
+                             [default: square]
+      --topology <TOPOLOGY>  bounded or torus, a torus wraps around its edges and needs
+                             explicit exits [default: bounded]
+      --input <FORMAT>       text or image [default: text], image reads a PNG, PPM or PGM
+                             image with dark walls, a green start and red exits
+      --threshold <0-255>    Brightness below which image cells are walls [default: 128]
+      --pixels-per-cell <N>  Width and height of a cell in the image input [default: 1]
+  -f, --format <FORMAT>      text, svg or png [default: text], svg draws the solution as a
+                             line, png is written to stdout as is
+      --style <STYLE>        Characters of the text output: emoji, ascii, box or half-block
+                             [default: emoji]
+      --cell-size <SIZE>     Width of a cell in the svg output or whole pixels in the png
+                             output [default: 20]
+      --margin <SIZE>        Empty space around the svg output [default: 0]
+  -h, --help                 Print this help
+
+Exit codes:
+  0  solved
+  1  no solution
+  2  invalid input
+  3  output error";
+
+/// Reason why a run failed, deciding the exit code.
+#[derive(Debug)]
+enum Failure {
+    /// Invalid arguments or maze data.
+    Input(String),
+    /// The output could not be written.
+    Output(String),
+}
 
-    pub trait AsChar {
-        fn as_char(&self) -> char;
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Failure::Input(message)
     }
+}
 
-    #[derive(Clone, Debug, Default, PartialEq)]
-    pub enum MazeCell {
-        #[default]
-        Wall,
-        Floor(FloorType),
-    }
+/// Solver selected on the command line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Solver {
+    Search(SolveAlgorithm),
+    AStar,
+    Keys,
+}
 
-    impl AsChar for MazeCell {
-        fn as_char(&self) -> char {
-            match self {
-                MazeCell::Wall => '⬜',
-                MazeCell::Floor(f) => f.as_char(),
-            }
-        }
-    }
+#[derive(Debug)]
+struct Args {
+    file: Option<String>,
+    start: Option<(usize, usize)>,
+    solver: Solver,
+    goals: Vec<(usize, usize)>,
+    heuristic: Heuristic,
+    movement: Movement,
+    shape: Shape,
+    topology: Topology,
+    input: Input,
+    threshold: u8,
+    pixels_per_cell: usize,
+    format: Format,
+    style: RenderStyle,
+    cell_size: f64,
+    margin: f64,
+}
 
-    impl MazeCell {
-        fn from_bool(value: bool) -> Self {
-            if value {
-                MazeCell::Floor(FloorType::default())
-            } else {
-                MazeCell::default()
-            }
-        }
-    }
+/// Input format selected on the command line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Input {
+    Text,
+    Image,
+}
 
-    #[derive(Clone, Debug, Default, PartialEq)]
-    pub enum FloorType {
-        #[default]
-        Floor,
-        Start,
-        Path,
-    }
+/// Output format selected on the command line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Text,
+    Svg,
+    Png,
+}
 
-    impl AsChar for FloorType {
-        fn as_char(&self) -> char {
-            match self {
-                FloorType::Floor => '⬛',
-                FloorType::Start => '❌',
-                FloorType::Path => '👣',
+impl Args {
+    /// Parses the command line arguments, `Ok(None)` if help was requested.
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Args>, String> {
+        let mut file = None;
+        let mut start = None;
+        let mut solver = Solver::Search(SolveAlgorithm::default());
+        let mut goals = Vec::new();
+        let mut heuristic = None;
+        let mut movement = Movement::default();
+        let mut corners = None;
+        let mut shape = Shape::default();
+        let mut topology = Topology::default();
+        let mut input = Input::Text;
+        let mut threshold = 128;
+        let mut pixels_per_cell = 1;
+        let mut format = Format::Text;
+        let mut style = RenderStyle::default();
+        let mut cell_size = 20.0;
+        let mut margin = 0.0;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("Missing value for '{}'", arg))
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-s" | "--start" => start = Some(parse_position(&value()?)?),
+                "-a" | "--solver" => {
+                    solver = match value()?.as_str() {
+                        "dfs" => Solver::Search(SolveAlgorithm::DepthFirst),
+                        "bfs" => Solver::Search(SolveAlgorithm::BreadthFirst),
+                        "dijkstra" => Solver::Search(SolveAlgorithm::Dijkstra),
+                        "astar" => Solver::AStar,
+                        "keys" => Solver::Keys,
+                        other => return Err(format!("Unknown solver '{}'", other)),
+                    }
+                }
+                "-g" | "--goal" => goals.push(parse_position(&value()?)?),
+                "--heuristic" => {
+                    heuristic = Some(match value()?.as_str() {
+                        "manhattan" => Heuristic::Manhattan,
+                        "chebyshev" => Heuristic::Chebyshev,
+                        "euclidean" => Heuristic::Euclidean,
+                        "zero" => Heuristic::Zero,
+                        other => return Err(format!("Unknown heuristic '{}'", other)),
+                    })
+                }
+                "-m" | "--movement" => {
+                    movement = match value()?.as_str() {
+                        "orthogonal" => Movement::Orthogonal,
+                        "diagonal" => Movement::Diagonal(CornerCutting::default()),
+                        "sliding" => Movement::Sliding,
+                        other => return Err(format!("Unknown movement '{}'", other)),
+                    }
+                }
+                "--corners" => {
+                    corners = Some(match value()?.as_str() {
+                        "never" => CornerCutting::Never,
+                        "one-wall" => CornerCutting::OneWall,
+                        "always" => CornerCutting::Always,
+                        other => return Err(format!("Unknown corner rule '{}'", other)),
+                    })
+                }
+                "--shape" => {
+                    shape = match value()?.as_str() {
+                        "square" => Shape::Square,
+                        "hex" => Shape::Hex,
+                        other => return Err(format!("Unknown shape '{}'", other)),
+                    }
+                }
+                "--topology" => {
+                    topology = match value()?.as_str() {
+                        "bounded" => Topology::Bounded,
+                        "torus" => Topology::Torus,
+                        other => return Err(format!("Unknown topology '{}'", other)),
+                    }
+                }
+                "--input" => {
+                    input = match value()?.as_str() {
+                        "text" => Input::Text,
+                        "image" => Input::Image,
+                        other => return Err(format!("Unknown input format '{}'", other)),
+                    }
+                }
+                "--threshold" => {
+                    let value = value()?;
+                    threshold = value
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid threshold '{}'", value))?;
+                }
+                "--pixels-per-cell" => {
+                    let value = value()?;
+                    pixels_per_cell = value
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid pixels per cell '{}'", value))?;
+                }
+                "-f" | "--format" => {
+                    format = match value()?.as_str() {
+                        "text" => Format::Text,
+                        "svg" => Format::Svg,
+                        "png" => Format::Png,
+                        other => return Err(format!("Unknown format '{}'", other)),
+                    }
+                }
+                "--style" => {
+                    style = match value()?.as_str() {
+                        "emoji" => RenderStyle::Emoji,
+                        "ascii" => RenderStyle::Ascii,
+                        "box" => RenderStyle::BoxDrawing,
+                        "half-block" => RenderStyle::HalfBlock,
+                        other => return Err(format!("Unknown style '{}'", other)),
+                    }
+                }
+                "--cell-size" => cell_size = parse_size(&value()?)?,
+                "--margin" => margin = parse_size(&value()?)?,
+                _ if arg.starts_with('-') && arg != "-" => {
+                    return Err(format!("Unknown option '{}'", arg))
+                }
+                _ if file.is_none() => file = Some(arg),
+                _ => return Err(format!("Unexpected argument '{}'", arg)),
             }
         }
-    }
-
-    #[derive(Debug)]
-    pub struct Maze {
-        /// `false` represents walls, `true` represents floor
-        map: Vec<Vec<MazeCell>>, // false represents walls, true represents floor
-        width: usize,
-        height: usize,
-        start_x: usize,
-        start_y: usize,
 
-        visited: Option<Vec<Vec<bool>>>,
-    }
-
-    pub trait Solvable {
-        fn solve(&mut self) -> Result<bool, String>;
-    }
-
-    impl Maze {
-        /// Character for walls: 'X'
-        const INPUT_WALL: char = 'X';
-        /// Character for floor: ' '
-        const INPUT_FLOOR: char = ' ';
-
-        /// Creates a new [`Maze`].
-        pub fn new(
-            mut map: Vec<Vec<MazeCell>>,
-            start_x: usize,
-            start_y: usize,
-        ) -> Result<Maze, String> {
-            let height = map.len();
-            let width = map.iter().map(|row| row.len()).max().unwrap_or_default();
-
-            if height < 3 || width < 3 {
-                return Err("Maze is too small. Minimum 3x3".to_string());
+        // options that would be silently ignored
+        if !matches!(solver, Solver::AStar) {
+            if !goals.is_empty() {
+                return Err("--goal needs the astar solver".to_string());
             }
-
-            // make sure all rows are the same length
-            for row in map.iter_mut() {
-                // fill shorter rows with default MazeCell (Wall)
-                row.resize(width, MazeCell::default())
+            if heuristic.is_some() {
+                return Err("--heuristic needs the astar solver".to_string());
             }
-
-            Self::validate_start(&map, start_x, start_y)?;
-
-            Ok(Self {
-                map,
-                width,
-                height,
-                start_x,
-                start_y,
-                visited: None,
-            })
         }
-
-        pub fn new_from_bool_array(
-            map: Vec<Vec<bool>>,
-            start_x: usize,
-            start_y: usize,
-        ) -> Result<Maze, String> {
-            let new_map = map
-                .into_iter()
-                .map(|row| row.into_iter().map(MazeCell::from_bool).collect())
-                .collect();
-            Maze::new(new_map, start_x, start_y)
+        if corners.is_some() && !matches!(movement, Movement::Diagonal(_)) {
+            return Err("--corners needs diagonal movement".to_string());
         }
 
-        pub fn new_from_str_array(
-            map: Vec<&str>,
-            start_x: usize,
-            start_y: usize,
-        ) -> Result<Maze, String> {
-            let grid: Result<Vec<Vec<MazeCell>>, String> = map
-                .iter()
-                .map(|&row| {
-                    row.chars()
-                        .map(|c| match c {
-                            Maze::INPUT_FLOOR => Ok(MazeCell::Floor(FloorType::default())),
-                            Maze::INPUT_WALL => Ok(MazeCell::Wall),
-                            _ => Err(format!("Unknown character '{}' in provided maze data!", c)),
-                        })
-                        .collect::<Result<Vec<MazeCell>, String>>()
-                })
-                .collect();
+        Ok(Some(Args {
+            file: file.filter(|file| file != "-"),
+            start,
+            solver,
+            goals,
+            heuristic: heuristic.unwrap_or_default(),
+            movement: match movement {
+                Movement::Diagonal(_) => Movement::Diagonal(corners.unwrap_or_default()),
+                other => other,
+            },
+            shape,
+            topology,
+            input,
+            threshold,
+            pixels_per_cell,
+            format,
+            style,
+            cell_size,
+            margin,
+        }))
+    }
+}
 
-            // propagate any errors
-            let grid = grid?;
+/// Parses a position given as `X,Y`.
+fn parse_position(value: &str) -> Result<(usize, usize), String> {
+    value
+        .split_once(',')
+        .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)))
+        .ok_or_else(|| format!("Invalid position '{}', expected X,Y", value))
+}
 
-            Maze::new(grid, start_x, start_y)
-        }
+/// Parses a non-negative size of the svg output.
+fn parse_size(value: &str) -> Result<f64, String> {
+    value
+        .trim()
+        .parse()
+        .ok()
+        .filter(|size: &f64| size.is_finite() && *size >= 0.0)
+        .ok_or_else(|| format!("Invalid size '{}'", value))
+}
 
-        pub fn new_from_str(map: &str, start_x: usize, start_y: usize) -> Result<Maze, String> {
-            let array_map = map.split('\n').collect::<Vec<&str>>();
-            Maze::new_from_str_array(array_map, start_x, start_y)
+fn read_input(file: Option<&str>) -> io::Result<Vec<u8>> {
+    match file {
+        Some(path) => fs::read(path),
+        None => {
+            let mut input = Vec::new();
+            io::stdin().read_to_end(&mut input)?;
+            Ok(input)
         }
+    }
+}
 
-        fn solve_from(&mut self, x: usize, y: usize) -> Result<bool, String> {
-            if let (Some(cell), Some(visited)) = (
-                self.map.get_mut(y).and_then(|row| row.get_mut(x)),
-                self.visited
-                    .as_mut()
-                    .and_then(|v| v.get_mut(y).and_then(|row| row.get_mut(x))),
-            ) {
-                if cell == &MazeCell::Wall {
-                    // on wall
-                    return Ok(false);
-                }
-
-                if *visited {
-                    // already visited
-                    return Ok(false);
-                }
-
-                *visited = true;
-
-                if x == 0 || x >= self.width - 1 || y == 0 || y >= self.height - 1 {
-                    // found edge (finish)
-                    *cell = MazeCell::Floor(FloorType::Path);
-                    return Ok(true);
-                }
-
-                // Try to solve from neighboring positions
-                for (next_x, next_y) in [
-                    (x.wrapping_sub(1), y),
-                    (x + 1, y),
-                    (x, y.wrapping_sub(1)),
-                    (x, y + 1),
-                ] {
-                    if self.solve_from(next_x, next_y)? {
-                        //*cell = MazeCell::Floor(FloorType::Path);  // NOTE: here not possible because of borrow checker
-
-                        if let Some(cell) = self.map.get_mut(y).and_then(|row| row.get_mut(x)) {
-                            *cell = MazeCell::Floor(FloorType::Path);
-                            return Ok(true);
-                        } else {
-                            return Err(format!("Starting position ({}, {}) out of bounds", x, y));
-                        }
-                    }
+/// Reads the maze in the input format given on the command line.
+fn load(input: Vec<u8>, args: &Args) -> Result<Maze, String> {
+    let maze = match args.input {
+        Input::Text => {
+            let input =
+                String::from_utf8(input).map_err(|e| format!("Error while reading maze: {}", e))?;
+            match args.start {
+                Some((start_x, start_y)) => {
+                    Maze::new_from_str_array(input.lines().collect(), start_x, start_y)
                 }
-                Ok(false)
-            } else {
-                Err(format!("Starting position ({}, {}) out of bounds", x, y))
+                None => input.parse::<Maze>(),
             }
         }
-
-        pub fn width(&self) -> usize {
-            self.width
-        }
-
-        pub fn height(&self) -> usize {
-            self.height
-        }
-
-        pub fn start_x(&self) -> usize {
-            self.start_x
+        Input::Image => {
+            let mut import = ImageImport::default()
+                .with_threshold(args.threshold)
+                .with_cell_size(args.pixels_per_cell);
+            if let Some((start_x, start_y)) = args.start {
+                import = import.with_start(start_x, start_y);
+            }
+            Maze::from_image(&input, &import)
         }
+    };
+    maze.map_err(|e| format!("Error while creating maze: {}", e))
+}
 
-        pub fn start_y(&self) -> usize {
-            self.start_y
+fn print(maze: &Maze, path: &[(usize, usize)], args: &Args) -> Result<(), Failure> {
+    let output = match args.format {
+        Format::Text => maze.to_string_with(args.style).into_bytes(),
+        Format::Svg => {
+            let style = SvgStyle::default()
+                .with_cell_size(args.cell_size)
+                .with_margin(args.margin);
+            maze.to_svg_with(&style, path).into_bytes()
         }
-
-        /// Sets the start of this [`Maze`].
-        ///
-        /// # Errors
-        ///
-        /// This function will return an error if starting position is on a wall.
-        pub fn set_start(mut self, start_x: usize, start_y: usize) -> Result<Self, String> {
-            Self::validate_start(&self.map, start_x, start_y)?;
-            self.start_x = start_x;
-            self.start_y = start_y;
-            Ok(self)
+        Format::Png => {
+            if args.cell_size.fract() != 0.0 {
+                return Err(format!("Cell size {} is not whole pixels", args.cell_size).into());
+            }
+            maze.to_png(args.cell_size as usize, path)
+                .map_err(|e| format!("Error while drawing maze: {}", e))?
         }
+    };
+    io::stdout()
+        .write_all(&output)
+        .map_err(|e| Failure::Output(format!("Error while writing output: {}", e)))
+}
 
-        fn validate_start(
-            map: &[Vec<MazeCell>],
-            start_x: usize,
-            start_y: usize,
-        ) -> Result<(), String> {
-            if let Some(row_vec) = map.get(start_y) {
-                if let Some(value) = row_vec.get(start_x) {
-                    match &value {
-                        MazeCell::Wall => {
-                            Err("Starting position must not be on a wall!".to_string())
-                        }
-                        MazeCell::Floor(_) => Ok(()),
-                    }
+/// Loads, solves and prints the maze, `Ok(false)` if it has no solution.
+fn run(args: &Args) -> Result<bool, Failure> {
+    let input =
+        read_input(args.file.as_deref()).map_err(|e| format!("Error while reading maze: {}", e))?;
+
+    let maze = load(input, args)?
+        .with_movement(args.movement)
+        .with_shape(args.shape)
+        .with_topology(args.topology);
+
+    let goals = if args.goals.is_empty() {
+        maze.exits()
+    } else {
+        &args.goals
+    };
+
+    let path = match args.solver {
+        Solver::Search(algorithm) => maze.find_path_with(algorithm),
+        Solver::AStar => maze
+            .find_path_astar(goals, args.heuristic)
+            .map(|(path, _)| path),
+        Solver::Keys => maze.find_path_with_keys().map(|found| {
+            found.map(|found| {
+                let keys: Vec<String> = found.keys.iter().map(char::to_string).collect();
+                if keys.is_empty() {
+                    eprintln!("Keys: none");
                 } else {
-                    Err("start_x out of bounds!".to_string())
+                    eprintln!("Keys: {}", keys.join(", "));
                 }
-            } else {
-                Err("start_y out of bounds!".to_string())
-            }
-        }
+                found.path
+            })
+        }),
     }
-
-    impl fmt::Display for Maze {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            let mut s = String::new();
-
-            for (y, row) in self.map.iter().enumerate() {
-                for (x, cell) in row.iter().enumerate() {
-                    if x == self.start_x && y == self.start_y {
-                        // start position
-                        s.push(FloorType::Start.as_char())
-                    } else {
-                        s.push(cell.as_char())
-                    }
-                }
-                s.push('\n');
-            }
-
-            write!(f, "{}", s)
+    .map_err(|e| format!("Error while solving maze: {}", e))?;
+
+    match path {
+        Some(path) => {
+            let solved = maze
+                .with_path(&path)
+                .map_err(|e| format!("Error while marking path: {}", e))?;
+            print(&solved, &path, args)?;
+            Ok(true)
         }
-    }
-
-    impl Solvable for Maze {
-        fn solve(&mut self) -> Result<bool, String> {
-            self.visited = Some(vec![vec![false; self.width]; self.height]);
-            self.solve_from(self.start_x, self.start_y)
+        None => {
+            print(&maze, &[], args)?;
+            Ok(false)
         }
     }
 }
 
-fn main() {
-    use maze::Maze;
-    use maze::Solvable;
-
-    let mut mazes = Vec::new();
-
-    mazes.push(
-        Maze::new_from_bool_array(
-            vec![
-                vec![true, false, true],
-                vec![false, true, false],
-                vec![true, true, false],
-            ],
-            1,
-            1,
-        )
-        .expect("Error while creating maze!"),
-    );
-
-    mazes.push(
-        Maze::new_from_str_array(vec![" X ", "X X", "  X"], 1, 1)
-            .expect("Error while creating maze!")
-            .set_start(1, 2)
-            .expect("Error on update starting position"),
-    );
-
-    mazes.push(
-        Maze::new_from_str(
-            &("XXX  XX   \n".to_owned()
-                + "X     X  X\n"
-                + "X XX  XX X\n"
-                + "X   XXX   \n"
-                + "X    X  XX\n"
-                + "XX  XX  X \n"
-                + "X  X  X X \n"
-                + "X   X   XX\n"
-                + "X XXXX XXX\n"
-                + "XX  XX  XX"),
-            4,
-            4,
-        )
-        .expect("Error while creating maze!"),
-    );
-
-    mazes.push(
-        Maze::new_from_str(
-            &("XXXXXXXXX\n".to_owned()
-                + "XXXXXXXXX\n"
-                + "XXXXXXXXX\n"
-                + "XXX   XXX\n"
-                + "XXX   XXX\n"
-                + "XXX   XXX\n"
-                + "XXX   XXX\n"
-                + "XXXXXXXXX\n"
-                + "XXXXXXXXX\n"
-                + "XXXXXXXXX"),
-            4,
-            4,
-        )
-        .expect("Error while creating maze!"),
-    );
-
-    for mut maze in mazes {
-        println!(
-            "Maze: width: {}, height: {}, start: (x:{}|y:{})\n{}",
-            maze.width(),
-            maze.height(),
-            maze.start_x(),
-            maze.start_y(),
-            maze
-        );
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::from(EXIT_SOLVED);
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(EXIT_INVALID_INPUT);
+        }
+    };
 
-        if let Ok(true) = maze.solve() {
-            println!("Solution:\n{}", maze);
-        } else {
-            println!("No solution for this maze");
+    match run(&args) {
+        Ok(true) => ExitCode::from(EXIT_SOLVED),
+        Ok(false) => {
+            eprintln!("No solution for this maze");
+            ExitCode::from(EXIT_NO_SOLUTION)
+        }
+        Err(Failure::Input(e)) => {
+            eprintln!("{}", e);
+            ExitCode::from(EXIT_INVALID_INPUT)
+        }
+        Err(Failure::Output(e)) => {
+            eprintln!("{}", e);
+            ExitCode::from(EXIT_OUTPUT_ERROR)
         }
     }
 }
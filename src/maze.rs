@@ -0,0 +1,839 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::cell::{FloorType, MazeCell};
+use crate::error::MazeError;
+use crate::keys;
+use crate::movement::{Direction, Movement};
+use crate::render::RenderStyle;
+use crate::shape::{self, Shape, Topology};
+
+/// Partner of each portal cell, in both directions.
+pub(crate) type Portals = HashMap<(usize, usize), (usize, usize)>;
+
+/// Rectangular maze with a starting position.
+///
+/// Without explicit exits every floor cell on the border of the maze is an exit.
+#[derive(Clone, Debug)]
+pub struct Maze {
+    /// Cells indexed as `map[y][x]`, all rows have the same length
+    pub(crate) map: Vec<Vec<MazeCell>>,
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) start_x: usize,
+    pub(crate) start_y: usize,
+    /// Explicit exits, the border is used if empty
+    pub(crate) exits: Vec<(usize, usize)>,
+    pub(crate) movement: Movement,
+    pub(crate) shape: Shape,
+    pub(crate) topology: Topology,
+    /// Partner of each [`FloorType::Portal`] cell
+    pub(crate) portals: Portals,
+    /// Cells of a marked path that keep their own [`FloorType`], see [`Maze::mark_path`]
+    pub(crate) marked: BTreeSet<(usize, usize)>,
+}
+
+/// Cells and markers read from the text format.
+pub(crate) struct ParsedRows {
+    pub(crate) map: Vec<Vec<MazeCell>>,
+    pub(crate) start: Option<(usize, usize)>,
+    pub(crate) exits: Vec<(usize, usize)>,
+    /// Path on floor other than plain floor, from the marker lines
+    pub(crate) path: Vec<(usize, usize)>,
+}
+
+/// Start, exits and path of the marker lines after the rows of the text format, positions with
+/// `N` coordinates.
+///
+/// The rows mark the start and exits in place of plain floor and the path as `'.'`, these lines
+/// hold the rest: `@start X,Y`, `@exits X,Y ...` and `@path X,Y ...`.
+#[derive(Debug, Default)]
+pub(crate) struct Markers<const N: usize> {
+    pub(crate) start: Option<[usize; N]>,
+    pub(crate) exits: Vec<[usize; N]>,
+    pub(crate) path: Vec<[usize; N]>,
+}
+
+impl<const N: usize> Markers<N> {
+    /// Number of `lines` before the marker lines.
+    pub(crate) fn start_of(lines: &[&str]) -> usize {
+        lines
+            .iter()
+            .position(|line| line.starts_with(Maze::INPUT_MARKERS))
+            .unwrap_or(lines.len())
+    }
+
+    /// Reads the marker `lines`, `first` is the number of lines before them.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a line is not a marker line or a second
+    /// `@start` line.
+    pub(crate) fn parse(lines: &[&str], first: usize) -> Result<Self, MazeError> {
+        let mut markers = Self::default();
+        for (index, line) in lines.iter().enumerate() {
+            let invalid = MazeError::InvalidMarkers {
+                line: first + index + 1,
+            };
+            let mut words = line
+                .strip_prefix(Maze::INPUT_MARKERS)
+                .ok_or(invalid.clone())?
+                .split_whitespace();
+            let name = words.next();
+            let positions = words
+                .map(|word| {
+                    let position: Option<Vec<usize>> =
+                        word.split(',').map(|value| value.parse().ok()).collect();
+                    position
+                        .and_then(|position| position.try_into().ok())
+                        .ok_or(invalid.clone())
+                })
+                .collect::<Result<Vec<[usize; N]>, MazeError>>()?;
+            match (name, &positions[..]) {
+                (Some("start"), &[start]) if markers.start.is_none() => markers.start = Some(start),
+                (Some("exits"), _) => markers.exits.extend(positions),
+                (Some("path"), _) => markers.path.extend(positions),
+                _ => return Err(invalid),
+            }
+        }
+        Ok(markers)
+    }
+
+    /// Writes the marker lines, nothing if there are no markers.
+    pub(crate) fn write(&self) -> String {
+        let mut s = String::new();
+        for (name, positions) in [
+            ("start", self.start.as_slice()),
+            ("exits", &self.exits),
+            ("path", &self.path),
+        ] {
+            if positions.is_empty() {
+                continue;
+            }
+            let positions: Vec<String> = positions
+                .iter()
+                .map(|position| position.map(|value| value.to_string()).join(","))
+                .collect();
+            s.push_str(&format!(
+                "{}{} {}\n",
+                Maze::INPUT_MARKERS,
+                name,
+                positions.join(" ")
+            ));
+        }
+        s
+    }
+}
+
+impl Maze {
+    /// Character for walls: 'X'
+    const INPUT_WALL: char = 'X';
+    /// Character for floor: ' '
+    const INPUT_FLOOR: char = ' ';
+    /// Character for the start (on floor): 'S'
+    const INPUT_START: char = 'S';
+    /// Character for an exit (on floor): 'E'
+    const INPUT_EXIT: char = 'E';
+    /// Character for road: '='
+    const INPUT_ROAD: char = '=';
+    /// Character for mud: '%'
+    const INPUT_MUD: char = '%';
+    /// Character for water: '~'
+    const INPUT_WATER: char = '~';
+    /// Character for stairs: '#'
+    const INPUT_STAIRS: char = '#';
+    /// Character for ice: '*'
+    const INPUT_ICE: char = '*';
+    /// Characters for portals, or keys if there is a door with the same letter: 'a' to 'z',
+    /// except for 'v'
+    const INPUT_PORTAL: RangeInclusive<char> = 'a'..='z';
+    /// Characters for doors: 'A' to 'Z', except for the other uppercase characters and 'V'
+    const INPUT_DOOR: RangeInclusive<char> = 'A'..='Z';
+    /// Character for a one-way cell to the left: '<'
+    const INPUT_ONE_WAY_LEFT: char = '<';
+    /// Character for a one-way cell to the right: '>'
+    const INPUT_ONE_WAY_RIGHT: char = '>';
+    /// Character for a one-way cell upwards: '^'
+    const INPUT_ONE_WAY_UP: char = '^';
+    /// Character for a one-way cell downwards: 'v'
+    const INPUT_ONE_WAY_DOWN: char = 'v';
+    /// Character for a conveyor to the left, like the arrow on a number pad: '4'
+    const INPUT_CONVEYOR_LEFT: char = '4';
+    /// Character for a conveyor to the right: '6'
+    const INPUT_CONVEYOR_RIGHT: char = '6';
+    /// Character for a conveyor upwards: '8'
+    const INPUT_CONVEYOR_UP: char = '8';
+    /// Character for a conveyor downwards: '2'
+    const INPUT_CONVEYOR_DOWN: char = '2';
+    /// Character for a cell of a solution: '.'
+    const INPUT_PATH: char = '.';
+    /// Character starting the marker lines after the rows, see [`Markers`]: '@'
+    const INPUT_MARKERS: char = '@';
+
+    /// Creates a new [`Maze`].
+    ///
+    /// Shorter rows are filled up with walls.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the maze is smaller than 3x3, the starting
+    /// position is out of bounds or on a wall, or a [`FloorType::Portal`] has no single partner.
+    pub fn new(
+        mut map: Vec<Vec<MazeCell>>,
+        start_x: usize,
+        start_y: usize,
+    ) -> Result<Maze, MazeError> {
+        let height = map.len();
+        let width = map.iter().map(|row| row.len()).max().unwrap_or_default();
+
+        if height < 3 || width < 3 {
+            return Err(MazeError::TooSmall { width, height });
+        }
+
+        // make sure all rows are the same length
+        for row in map.iter_mut() {
+            // fill shorter rows with default MazeCell (Wall)
+            row.resize(width, MazeCell::default())
+        }
+
+        Self::validate_start(&map, start_x, start_y)?;
+        let portals = Self::pair_portals(&map)?;
+
+        Ok(Self {
+            map,
+            width,
+            height,
+            start_x,
+            start_y,
+            exits: Vec::new(),
+            movement: Movement::default(),
+            shape: Shape::default(),
+            topology: Topology::default(),
+            portals,
+            marked: BTreeSet::new(),
+        })
+    }
+
+    /// Maps every [`FloorType::Portal`] cell to the other cell with the same label.
+    pub(crate) fn pair_portals(map: &[Vec<MazeCell>]) -> Result<Portals, MazeError> {
+        // ordered by label, so the error names the same portal every time
+        let mut labels: BTreeMap<char, Vec<(usize, usize)>> = BTreeMap::new();
+        for (y, row) in map.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if let MazeCell::Floor(FloorType::Portal(label)) = cell {
+                    labels.entry(*label).or_default().push((x, y));
+                }
+            }
+        }
+
+        let mut portals = HashMap::new();
+        for (portal, cells) in labels {
+            let [a, b] = cells[..] else {
+                return Err(MazeError::UnpairedPortal { portal });
+            };
+            portals.insert(a, b);
+            portals.insert(b, a);
+        }
+        Ok(portals)
+    }
+
+    /// Creates a new [`Maze`] where `false` represents walls and `true` represents floor.
+    ///
+    /// # Errors
+    ///
+    /// See [`Maze::new`].
+    pub fn new_from_bool_array(
+        map: Vec<Vec<bool>>,
+        start_x: usize,
+        start_y: usize,
+    ) -> Result<Maze, MazeError> {
+        let new_map = map
+            .into_iter()
+            .map(|row| row.into_iter().map(MazeCell::from_bool).collect())
+            .collect();
+        Maze::new(new_map, start_x, start_y)
+    }
+
+    /// Creates a new [`Maze`] from rows where `'X'` represents walls and `' '` represents floor.
+    ///
+    /// The floor may also be marked with `'S'` for the start and `'E'` for explicit exits.
+    /// The given starting position takes precedence over an `'S'` marker. Terrain with a
+    /// different [`FloorType::cost`] is written as `'='` (road), `'%'` (mud) and `'~'` (water),
+    /// `'#'` marks [`FloorType::Stairs`] and `'*'` [`FloorType::Ice`]. Lowercase letters `'a'`
+    /// to `'z'` are portals, each letter must be used by exactly two cells. Uppercase letters
+    /// other than `'E'`, `'S'` and `'X'` are doors, which turn the lowercase letter into their
+    /// key instead of a portal. One-way cells are written as `'<'`, `'>'`, `'^'` and `'v'`,
+    /// which is therefore neither a portal nor a key, conveyors as `'4'`, `'6'`, `'8'` and `'2'`
+    /// like the arrows on a number pad.
+    ///
+    /// A solution is marked with `'.'`. The rows may be followed by lines starting with `'@'`
+    /// for markers on other floor: `@start X,Y`, `@exits X,Y X,Y ...` and `@path X,Y ...`.
+    /// This is the format of [`RenderStyle::Ascii`], so its output reads back into a maze that
+    /// renders the same.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a row contains any other character, a line after
+    /// the rows is not a marker line or there is more than one start, see also [`Maze::new`].
+    pub fn new_from_str_array(
+        map: Vec<&str>,
+        start_x: usize,
+        start_y: usize,
+    ) -> Result<Maze, MazeError> {
+        let parsed = Self::parse_rows(&map)?;
+        let mut maze = Maze::new(parsed.map, start_x, start_y)?.set_exits(parsed.exits)?;
+        maze.mark_path(&parsed.path)?;
+        Ok(maze)
+    }
+
+    pub(crate) fn parse_rows(lines: &[&str]) -> Result<ParsedRows, MazeError> {
+        use Direction::{Down, Left, Right, Up};
+
+        let rows = Markers::<2>::start_of(lines);
+        let (map, markers) = lines.split_at(rows);
+        let markers = Markers::<2>::parse(markers, rows)?;
+
+        let mut start = None;
+        let mut exits = Vec::new();
+
+        let grid: Result<Vec<Vec<MazeCell>>, MazeError> = map
+            .iter()
+            .enumerate()
+            .map(|(y, &row)| {
+                row.chars()
+                    .enumerate()
+                    .map(|(x, c)| match c {
+                        Maze::INPUT_FLOOR => Ok(MazeCell::Floor(FloorType::default())),
+                        Maze::INPUT_WALL => Ok(MazeCell::Wall),
+                        Maze::INPUT_PATH => Ok(MazeCell::Floor(FloorType::Path)),
+                        Maze::INPUT_ROAD => Ok(MazeCell::Floor(FloorType::Road)),
+                        Maze::INPUT_MUD => Ok(MazeCell::Floor(FloorType::Mud)),
+                        Maze::INPUT_WATER => Ok(MazeCell::Floor(FloorType::Water)),
+                        Maze::INPUT_STAIRS => Ok(MazeCell::Floor(FloorType::Stairs)),
+                        Maze::INPUT_ICE => Ok(MazeCell::Floor(FloorType::Ice)),
+                        Maze::INPUT_START => match start.replace((x, y)) {
+                            None => Ok(MazeCell::Floor(FloorType::default())),
+                            Some(_) => Err(MazeError::MultipleStarts { x, y }),
+                        },
+                        Maze::INPUT_EXIT => {
+                            exits.push((x, y));
+                            Ok(MazeCell::Floor(FloorType::default()))
+                        }
+                        Maze::INPUT_ONE_WAY_LEFT => Ok(MazeCell::Floor(FloorType::OneWay(Left))),
+                        Maze::INPUT_ONE_WAY_RIGHT => Ok(MazeCell::Floor(FloorType::OneWay(Right))),
+                        Maze::INPUT_ONE_WAY_UP => Ok(MazeCell::Floor(FloorType::OneWay(Up))),
+                        Maze::INPUT_ONE_WAY_DOWN => Ok(MazeCell::Floor(FloorType::OneWay(Down))),
+                        Maze::INPUT_CONVEYOR_LEFT => Ok(MazeCell::Floor(FloorType::Conveyor(Left))),
+                        Maze::INPUT_CONVEYOR_RIGHT => {
+                            Ok(MazeCell::Floor(FloorType::Conveyor(Right)))
+                        }
+                        Maze::INPUT_CONVEYOR_UP => Ok(MazeCell::Floor(FloorType::Conveyor(Up))),
+                        Maze::INPUT_CONVEYOR_DOWN => Ok(MazeCell::Floor(FloorType::Conveyor(Down))),
+                        c if Maze::INPUT_PORTAL.contains(&c) => {
+                            Ok(MazeCell::Floor(FloorType::Portal(c)))
+                        }
+                        c if Maze::INPUT_DOOR.contains(&c)
+                            && c.to_ascii_lowercase() != Maze::INPUT_ONE_WAY_DOWN =>
+                        {
+                            Ok(MazeCell::Floor(FloorType::Door(c.to_ascii_lowercase())))
+                        }
+                        _ => Err(MazeError::UnknownCharacter {
+                            character: c,
+                            line: y + 1,
+                            column: x + 1,
+                        }),
+                    })
+                    .collect::<Result<Vec<MazeCell>, MazeError>>()
+            })
+            .collect();
+
+        // propagate any errors
+        let mut map = grid?;
+
+        // letters with a door are keys
+        let doors: Vec<char> = map
+            .iter()
+            .flatten()
+            .filter_map(|cell| match cell {
+                MazeCell::Floor(FloorType::Door(label)) => Some(*label),
+                _ => None,
+            })
+            .collect();
+        for cell in map.iter_mut().flatten() {
+            if let MazeCell::Floor(FloorType::Portal(label)) = cell {
+                if doors.contains(label) {
+                    *cell = MazeCell::Floor(FloorType::Key(*label));
+                }
+            }
+        }
+
+        if let Some([x, y]) = markers.start {
+            if start.replace((x, y)).is_some() {
+                return Err(MazeError::MultipleStarts { x, y });
+            }
+        }
+        exits.extend(markers.exits.into_iter().map(|[x, y]| (x, y)));
+        let path = markers.path.into_iter().map(|[x, y]| (x, y)).collect();
+
+        Ok(ParsedRows {
+            map,
+            start,
+            exits,
+            path,
+        })
+    }
+
+    /// Creates a new [`Maze`] from lines in the format of [`Maze::new_from_str_array`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Maze::new_from_str_array`].
+    pub fn new_from_str(map: &str, start_x: usize, start_y: usize) -> Result<Maze, MazeError> {
+        let array_map = map.lines().collect::<Vec<&str>>();
+        Maze::new_from_str_array(array_map, start_x, start_y)
+    }
+
+    pub(crate) fn is_border(&self, x: usize, y: usize) -> bool {
+        x == 0 || x >= self.width - 1 || y == 0 || y >= self.height - 1
+    }
+
+    /// Whether a solver has reached an exit at `x`, `y`.
+    pub(crate) fn is_exit(&self, x: usize, y: usize) -> bool {
+        if self.exits.is_empty() && self.topology == Topology::Bounded {
+            self.is_border(x, y)
+        } else {
+            self.exits.contains(&(x, y))
+        }
+    }
+
+    /// Checks that the edges of a [`Topology::Torus`] fit together, which the shifted rows of a
+    /// [`Shape::Hex`] maze only do with an even height.
+    pub(crate) fn validate_topology(&self) -> Result<(), MazeError> {
+        match (self.shape, self.topology) {
+            (Shape::Hex, Topology::Torus) if self.height % 2 == 1 => Err(MazeError::OddHexTorus {
+                height: self.height,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that a solver can tell where the exits are, see also [`Maze::validate_topology`].
+    pub(crate) fn validate_exits(&self) -> Result<(), MazeError> {
+        self.validate_topology()?;
+        match self.topology {
+            Topology::Torus if self.exits.is_empty() => Err(MazeError::MissingExits),
+            _ => Ok(()),
+        }
+    }
+
+    /// Moves a position next to the grid, as given by [`Maze::neighbours`], into the grid on a
+    /// [`Topology::Torus`].
+    fn wrap(&self, x: usize, y: usize) -> (usize, usize) {
+        let wrap = |value: usize, size: usize| match value {
+            usize::MAX => size - 1,
+            value if value == size => 0,
+            value => value,
+        };
+        match self.topology {
+            Topology::Bounded => (x, y),
+            Topology::Torus => (wrap(x, self.width), wrap(y, self.height)),
+        }
+    }
+
+    /// Horizontal and vertical distance between two positions, the shorter way around on a
+    /// [`Topology::Torus`].
+    pub(crate) fn distance(&self, from: (usize, usize), to: (usize, usize)) -> (usize, usize) {
+        let (dx, dy) = (from.0.abs_diff(to.0), from.1.abs_diff(to.1));
+        match self.topology {
+            Topology::Bounded => (dx, dy),
+            Topology::Torus => (dx.min(self.width - dx), dy.min(self.height - dy)),
+        }
+    }
+
+    /// Whether the cell at `x`, `y` can be entered while holding `keys`, see [`keys::bit`].
+    fn is_passable(&self, x: usize, y: usize, keys: u32) -> bool {
+        match self.map.get(y).and_then(|row| row.get(x)) {
+            Some(MazeCell::Floor(FloorType::Door(label))) => keys & keys::bit(*label) != 0,
+            Some(MazeCell::Floor(_)) => true,
+            Some(MazeCell::Wall) | None => false,
+        }
+    }
+
+    /// Whether a step from `x`, `y` to the unwrapped position `to` follows the [`Direction`] of
+    /// a one-way cell or conveyor, other cells can be left in every direction.
+    fn may_leave(&self, x: usize, y: usize, to: (usize, usize)) -> bool {
+        let direction = match &self.map[y][x] {
+            MazeCell::Floor(FloorType::OneWay(direction) | FloorType::Conveyor(direction)) => {
+                direction
+            }
+            _ => return true,
+        };
+        let (left, up) = (x.wrapping_sub(1), y.wrapping_sub(1));
+        match (direction, self.shape) {
+            (Direction::Left, _) => to == (left, y),
+            (Direction::Right, _) => to == (x + 1, y),
+            (Direction::Up, Shape::Square) => to == (x, up),
+            (Direction::Down, Shape::Square) => to == (x, y + 1),
+            (Direction::Up, Shape::Hex) => to.1 == up,
+            (Direction::Down, Shape::Hex) => to.1 == y + 1,
+        }
+    }
+
+    /// Floor cells reachable in one step from `x`, `y` with the [`Shape`], [`Movement`] and
+    /// [`Topology`] of this maze, doors are closed.
+    ///
+    /// For square cells the order is left, right, up, down, followed by the diagonals up-left,
+    /// up-right, down-left, down-right. Hexagons use the order of [`shape::hex_neighbours`].
+    /// The partner of a portal comes last.
+    pub(crate) fn neighbours(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours_holding(x, y, 0)
+    }
+
+    /// Floor cells reachable in one step like [`Maze::neighbours`], opening the doors of
+    /// `keys`.
+    ///
+    /// A step onto ice, or any floor with [`Movement::Sliding`], continues in the same direction
+    /// until the next step is blocked.
+    pub(crate) fn neighbours_holding(
+        &self,
+        x: usize,
+        y: usize,
+        keys: u32,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let directions = match (self.shape, self.movement) {
+            (Shape::Hex, _) => 0..6,
+            (Shape::Square, Movement::Diagonal(_)) => 0..8,
+            (Shape::Square, Movement::Orthogonal | Movement::Sliding) => 0..4,
+        };
+
+        directions
+            .filter_map(move |direction| {
+                let mut position = self.step((x, y), direction, keys)?;
+                // a slide around a torus may never stop
+                for _ in 0..self.width * self.height {
+                    if !self.slides(position) {
+                        return Some(position);
+                    }
+                    match self.step(position, direction, keys) {
+                        Some(next) => position = next,
+                        None => return Some(position),
+                    }
+                }
+                None
+            })
+            .chain(self.portal(x, y))
+    }
+
+    /// Floor cell reached by a single step from `from` in `direction`, `None` if the step is
+    /// blocked.
+    ///
+    /// Square directions are left, right, up, down, up-left, up-right, down-left, down-right,
+    /// hexagons use the order of [`shape::hex_neighbours`].
+    fn step(&self, from: (usize, usize), direction: usize, keys: u32) -> Option<(usize, usize)> {
+        let (x, y) = from;
+        let (left, up) = (x.wrapping_sub(1), y.wrapping_sub(1));
+        let to = match self.shape {
+            Shape::Square => [
+                (left, y),
+                (x + 1, y),
+                (x, up),
+                (x, y + 1),
+                (left, up),
+                (x + 1, up),
+                (left, y + 1),
+                (x + 1, y + 1),
+            ][direction],
+            Shape::Hex => shape::hex_neighbours(x, y)[direction],
+        };
+        if !self.may_leave(x, y, to) {
+            return None;
+        }
+        if let (Shape::Square, Movement::Diagonal(corners), 4..) =
+            (self.shape, self.movement, direction)
+        {
+            // the two cells passed on the way
+            let open = [(to.0, y), (x, to.1)]
+                .into_iter()
+                .map(|(x, y)| self.wrap(x, y))
+                .filter(|&(x, y)| self.is_passable(x, y, keys))
+                .count();
+            if !corners.allows(open) {
+                return None;
+            }
+        }
+        let (to_x, to_y) = self.wrap(to.0, to.1);
+        self.is_passable(to_x, to_y, keys).then_some((to_x, to_y))
+    }
+
+    /// Whether a walker keeps sliding after a step onto the floor cell at `position`.
+    fn slides(&self, (x, y): (usize, usize)) -> bool {
+        self.movement == Movement::Sliding || self.map[y][x] == MazeCell::Floor(FloorType::Ice)
+    }
+
+    /// Returns the partner of the portal at `x`, `y`, `None` if there is no portal.
+    pub fn portal(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        self.portals.get(&(x, y)).copied()
+    }
+
+    /// Returns a copy of this [`Maze`] with `path` marked as [`FloorType::Path`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a position of `path` is out of bounds or on a wall.
+    pub fn with_path(&self, path: &[(usize, usize)]) -> Result<Maze, MazeError> {
+        let mut maze = self.clone();
+        maze.mark_path(path)?;
+        Ok(maze)
+    }
+
+    /// Returns the total cost of walking along `path`, see [`FloorType::cost`].
+    ///
+    /// The first position is where the walk starts, so its cost is not included. Jumping from
+    /// a portal to its partner and being moved on by a conveyor is free.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a position of `path` is out of bounds or on a wall.
+    pub fn path_cost(&self, path: &[(usize, usize)]) -> Result<usize, MazeError> {
+        let mut cost = 0;
+        for (index, &(x, y)) in path.iter().enumerate() {
+            match self.map.get(y).and_then(|row| row.get(x)) {
+                None => return Err(MazeError::PathOutOfBounds { x, y }),
+                Some(MazeCell::Wall) => return Err(MazeError::PathOnWall { x, y }),
+                Some(MazeCell::Floor(_)) if index == 0 => {}
+                Some(MazeCell::Floor(_)) => cost += self.move_cost(path[index - 1], (x, y)),
+            }
+        }
+        Ok(cost)
+    }
+
+    /// Cost of stepping onto the floor cell at `x`, `y`.
+    pub(crate) fn step_cost(&self, x: usize, y: usize) -> usize {
+        match &self.map[y][x] {
+            MazeCell::Floor(floor) => floor.cost(),
+            MazeCell::Wall => usize::MAX,
+        }
+    }
+
+    /// Cost of moving from `from` to the floor cell `to`, nothing for a portal jump or being
+    /// moved on by a conveyor.
+    pub(crate) fn move_cost(&self, from: (usize, usize), to: (usize, usize)) -> usize {
+        let conveyor = matches!(
+            self.map[from.1][from.0],
+            MazeCell::Floor(FloorType::Conveyor(_))
+        );
+        if conveyor || self.portal(from.0, from.1) == Some(to) {
+            0
+        } else {
+            self.step_cost(to.0, to.1)
+        }
+    }
+
+    /// Marks the plain floor of `path` as [`FloorType::Path`]. Every other kind of floor, like
+    /// portals, terrain and stairs, is kept and its position remembered instead, so a solved
+    /// maze is solved the same way again and its text form lists the whole path.
+    pub(crate) fn mark_path(&mut self, path: &[(usize, usize)]) -> Result<(), MazeError> {
+        // check the whole path first, so nothing is marked on error
+        for &(x, y) in path {
+            match self.map.get(y).and_then(|row| row.get(x)) {
+                None => return Err(MazeError::PathOutOfBounds { x, y }),
+                Some(MazeCell::Wall) => return Err(MazeError::PathOnWall { x, y }),
+                Some(MazeCell::Floor(_)) => {}
+            }
+        }
+        for &(x, y) in path {
+            let cell = &mut self.map[y][x];
+            if *cell == MazeCell::Floor(FloorType::Floor) {
+                *cell = MazeCell::Floor(FloorType::Path);
+            } else if !Maze::can_mark(cell) {
+                self.marked.insert((x, y));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the column of the starting position.
+    pub fn start_x(&self) -> usize {
+        self.start_x
+    }
+
+    /// Returns the row of the starting position.
+    pub fn start_y(&self) -> usize {
+        self.start_y
+    }
+
+    /// Returns the [`Movement`] used by the solvers.
+    pub fn movement(&self) -> Movement {
+        self.movement
+    }
+
+    /// Sets the [`Movement`] used by all solvers and [`Maze::reachable_cells`].
+    pub fn with_movement(mut self, movement: Movement) -> Self {
+        self.movement = movement;
+        self
+    }
+
+    /// Returns the [`Shape`] of the cells.
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    /// Sets the [`Shape`] of the cells, which changes the neighbours of every cell.
+    pub fn with_shape(mut self, shape: Shape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Returns the [`Topology`] of the edges.
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Sets the [`Topology`] of the edges, which changes the neighbours of the cells on the
+    /// border.
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Returns the explicit exits, empty if every floor cell on the border is an exit.
+    pub fn exits(&self) -> &[(usize, usize)] {
+        &self.exits
+    }
+
+    /// Sets the explicit exits of this [`Maze`], an empty list makes the border the exit again.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if an exit is out of bounds or on a wall.
+    pub fn set_exits(mut self, exits: Vec<(usize, usize)>) -> Result<Self, MazeError> {
+        for &(x, y) in &exits {
+            match self.map.get(y).and_then(|row| row.get(x)) {
+                None => return Err(MazeError::ExitOutOfBounds { x, y }),
+                Some(MazeCell::Wall) => return Err(MazeError::ExitOnWall { x, y }),
+                Some(MazeCell::Floor(_)) => {}
+            }
+        }
+        self.exits = exits;
+        Ok(self)
+    }
+
+    /// Sets the start of this [`Maze`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if starting position is out of bounds or on a wall.
+    pub fn set_start(mut self, start_x: usize, start_y: usize) -> Result<Self, MazeError> {
+        Self::validate_start(&self.map, start_x, start_y)?;
+        self.start_x = start_x;
+        self.start_y = start_y;
+        Ok(self)
+    }
+
+    fn validate_start(
+        map: &[Vec<MazeCell>],
+        start_x: usize,
+        start_y: usize,
+    ) -> Result<(), MazeError> {
+        match map.get(start_y).and_then(|row| row.get(start_x)) {
+            Some(MazeCell::Wall) => Err(MazeError::StartOnWall {
+                x: start_x,
+                y: start_y,
+            }),
+            Some(MazeCell::Floor(_)) => Ok(()),
+            None => Err(MazeError::StartOutOfBounds {
+                x: start_x,
+                y: start_y,
+            }),
+        }
+    }
+}
+
+impl Maze {
+    /// Whether the rows of the text format can mark the start, an exit or the path in place of
+    /// `cell` without losing anything, only plain floor and the path. Other cells are listed in
+    /// the [`Markers`] lines.
+    pub(crate) fn can_mark(cell: &MazeCell) -> bool {
+        matches!(
+            cell,
+            MazeCell::Floor(
+                FloorType::Floor | FloorType::Start | FloorType::Exit | FloorType::Path
+            )
+        )
+    }
+
+    /// Character of `cell` in the text format, the reverse of [`Maze::parse_rows`].
+    pub(crate) fn input_char(cell: &MazeCell) -> char {
+        use Direction::{Down, Left, Right, Up};
+
+        match cell {
+            MazeCell::Wall => Maze::INPUT_WALL,
+            MazeCell::Floor(floor) => match floor {
+                FloorType::Floor => Maze::INPUT_FLOOR,
+                FloorType::Start => Maze::INPUT_START,
+                FloorType::Exit => Maze::INPUT_EXIT,
+                FloorType::Path => Maze::INPUT_PATH,
+                FloorType::Road => Maze::INPUT_ROAD,
+                FloorType::Mud => Maze::INPUT_MUD,
+                FloorType::Water => Maze::INPUT_WATER,
+                FloorType::Stairs => Maze::INPUT_STAIRS,
+                FloorType::Ice => Maze::INPUT_ICE,
+                FloorType::Portal(label) | FloorType::Key(label) => *label,
+                FloorType::Door(label) => label.to_ascii_uppercase(),
+                FloorType::OneWay(Left) => Maze::INPUT_ONE_WAY_LEFT,
+                FloorType::OneWay(Right) => Maze::INPUT_ONE_WAY_RIGHT,
+                FloorType::OneWay(Up) => Maze::INPUT_ONE_WAY_UP,
+                FloorType::OneWay(Down) => Maze::INPUT_ONE_WAY_DOWN,
+                FloorType::Conveyor(Left) => Maze::INPUT_CONVEYOR_LEFT,
+                FloorType::Conveyor(Right) => Maze::INPUT_CONVEYOR_RIGHT,
+                FloorType::Conveyor(Up) => Maze::INPUT_CONVEYOR_UP,
+                FloorType::Conveyor(Down) => Maze::INPUT_CONVEYOR_DOWN,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Maze {
+    /// Renders the emoji of [`RenderStyle::Emoji`], see [`RenderStyle::Ascii`] for text that
+    /// reads back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let start = (self.start_x, self.start_y);
+        write!(
+            f,
+            "{}",
+            self.render(Some(start), &self.exits, RenderStyle::Emoji)
+        )
+    }
+}
+
+impl FromStr for Maze {
+    type Err = MazeError;
+
+    /// Parses a [`Maze`] in the format of [`Maze::new_from_str_array`], one row per line.
+    ///
+    /// The starting position is taken from the `'S'` marker. Output of [`RenderStyle::Ascii`]
+    /// parses into a maze with the same output.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parsed = Self::parse_rows(&s.lines().collect::<Vec<&str>>())?;
+        let (start_x, start_y) = parsed.start.ok_or(MazeError::MissingStart)?;
+        let mut maze = Maze::new(parsed.map, start_x, start_y)?.set_exits(parsed.exits)?;
+        maze.mark_path(&parsed.path)?;
+        Ok(maze)
+    }
+}
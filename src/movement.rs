@@ -0,0 +1,56 @@
+/// Moves a solver may take from one cell of a [`Maze`](crate::Maze) to the next.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Movement {
+    /// Steps to the left, right, up and down.
+    #[default]
+    Orthogonal,
+    /// Orthogonal and diagonal steps, diagonals past walls follow the [`CornerCutting`] rule.
+    Diagonal(CornerCutting),
+    /// Orthogonal steps that slide on like on ice until the next step is blocked, every slide
+    /// counts as a single step.
+    ///
+    /// [`SolveAlgorithm::BreadthFirst`](crate::SolveAlgorithm::BreadthFirst) finds the solution
+    /// with the fewest moves. Without it only [`FloorType::Ice`](crate::FloorType::Ice) slides.
+    Sliding,
+}
+
+/// Direction of a one-way or conveyor cell.
+///
+/// On [`Shape::Hex`](crate::Shape::Hex) cells [`Direction::Up`] and [`Direction::Down`] allow
+/// both neighbours in the row above or below.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    /// Towards lower `x`.
+    Left,
+    /// Towards higher `x`.
+    Right,
+    /// Towards lower `y`.
+    Up,
+    /// Towards higher `y`.
+    Down,
+}
+
+/// Rule for diagonal steps next to walls with [`Movement::Diagonal`].
+///
+/// A diagonal step passes the two cells orthogonally adjacent to both its ends.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CornerCutting {
+    /// Both passed cells must be floor, walls are never cut.
+    #[default]
+    Never,
+    /// At least one passed cell must be floor, the corner of a single wall may be cut.
+    OneWall,
+    /// Diagonal steps are allowed even between two walls.
+    Always,
+}
+
+impl CornerCutting {
+    /// Whether a diagonal step is allowed if `open` of the two passed cells are floor.
+    pub(crate) fn allows(&self, open: usize) -> bool {
+        match self {
+            CornerCutting::Never => open == 2,
+            CornerCutting::OneWall => open >= 1,
+            CornerCutting::Always => true,
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use crate::cell::{FloorType, MazeCell};
+use crate::error::MazeError;
+use crate::image::Image;
+use crate::maze::Maze;
+use crate::shape::Shape;
+use crate::zlib;
+
+/// Bytes every PNG file starts with.
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Largest width and height of a PNG image.
+const MAX_SIZE: usize = (1 << 31) - 1;
+
+impl Maze {
+    /// Renders this [`Maze`] as a PNG image of `cell_size` x `cell_size` pixels per cell, with
+    /// `path` marked like [`Maze::with_path`] does.
+    ///
+    /// The colours are the defaults of [`Maze::to_svg`]. Odd rows of a [`Shape::Hex`] maze are
+    /// shifted right by half a cell, the space left free has the colour of the walls.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `cell_size` is `0`, the image would be wider or
+    /// higher than the 2^31 - 1 pixels PNG allows or a position of `path` is out of bounds or
+    /// on a wall.
+    pub fn to_png(&self, cell_size: usize, path: &[(usize, usize)]) -> Result<Vec<u8>, MazeError> {
+        if cell_size == 0 {
+            return Err(MazeError::InvalidCellSize { size: cell_size });
+        }
+        let maze = self.with_path(path)?;
+
+        let shift = match self.shape {
+            Shape::Square => 0,
+            Shape::Hex => cell_size / 2,
+        };
+        let too_large = MazeError::ImageTooLarge { cell_size };
+        let (width, height) = self
+            .width
+            .checked_mul(cell_size)
+            .and_then(|width| width.checked_add(shift))
+            .zip(self.height.checked_mul(cell_size))
+            .filter(|&(width, height)| width <= MAX_SIZE && height <= MAX_SIZE)
+            .ok_or(too_large.clone())?;
+        let background = MazeCell::Wall.colour();
+        // every row of pixels starts with its filter type, always none
+        let stride = 3 * width + 1;
+        let mut pixels = Vec::new();
+        stride
+            .checked_mul(height)
+            .and_then(|size| pixels.try_reserve_exact(size).ok())
+            .ok_or(too_large)?;
+        for (y, row) in maze.map.iter().enumerate() {
+            let mut line = Vec::with_capacity(stride);
+            line.push(0);
+            let offset = if y % 2 == 1 { shift } else { 0 };
+            for _ in 0..offset {
+                line.extend_from_slice(&background);
+            }
+            for (x, cell) in row.iter().enumerate() {
+                let colour = if (x, y) == (self.start_x, self.start_y) {
+                    MazeCell::Floor(FloorType::Start).colour()
+                } else if self.exits.contains(&(x, y)) {
+                    MazeCell::Floor(FloorType::Exit).colour()
+                } else {
+                    cell.colour()
+                };
+                for _ in 0..cell_size {
+                    line.extend_from_slice(&colour);
+                }
+            }
+            while line.len() < stride {
+                line.extend_from_slice(&background);
+            }
+            for _ in 0..cell_size {
+                pixels.extend_from_slice(&line);
+            }
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(width as u32).to_be_bytes());
+        header.extend_from_slice(&(height as u32).to_be_bytes());
+        // 8 bit RGB, deflate, no interlacing
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut png = SIGNATURE.to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib::compress(&pixels, stride));
+        write_chunk(&mut png, b"IEND", &[]);
+        Ok(png)
+    }
+}
+
+/// Appends a chunk of `kind` with `data` and its checksum to `png`.
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(kind.iter().chain(data));
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// CRC-32 checksum of PNG chunks.
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Decodes a PNG image, transparent pixels are blended onto white.
+///
+/// # Errors
+///
+/// This function will return an error if `png` is damaged, interlaced or not a PNG image.
+pub(crate) fn decode(png: &[u8]) -> Result<Image, MazeError> {
+    let invalid = |reason| MazeError::InvalidImage { reason };
+    let mut rest = png
+        .strip_prefix(SIGNATURE.as_slice())
+        .ok_or(invalid("not a PNG image"))?;
+
+    let mut header = None;
+    let mut palette = Vec::new();
+    let mut data = Vec::new();
+    loop {
+        let length = match rest.get(..4) {
+            Some(length) => u32::from_be_bytes(length.try_into().unwrap_or_default()) as usize,
+            None => return Err(invalid("PNG image ends early")),
+        };
+        let chunk = length
+            .checked_add(12)
+            .and_then(|end| rest.get(4..end))
+            .ok_or(invalid("PNG image ends early"))?;
+        let (kind, body, crc) = (&chunk[..4], &chunk[4..length + 4], &chunk[length + 4..]);
+        if crc32(kind.iter().chain(body)).to_be_bytes() != crc {
+            return Err(invalid("wrong checksum of PNG chunk"));
+        }
+        rest = &rest[length + 12..];
+        match kind {
+            b"IHDR" => header = Some(body),
+            b"PLTE" => {
+                palette = body
+                    .chunks_exact(3)
+                    .map(|colour| [colour[0], colour[1], colour[2]])
+                    .collect()
+            }
+            b"IDAT" => data.extend_from_slice(body),
+            b"IEND" => break,
+            // other chunks don't change the pixels
+            _ => {}
+        }
+    }
+
+    let header = match header {
+        Some(header) if header.len() == 13 => header,
+        _ => return Err(invalid("PNG image without header")),
+    };
+    let size = |bytes: &[u8]| u32::from_be_bytes(bytes.try_into().unwrap_or_default()) as usize;
+    let (width, height) = (size(&header[..4]), size(&header[4..8]));
+    let (depth, colour_type) = (header[8] as usize, header[9]);
+    if header[10] != 0 || header[11] != 0 {
+        return Err(invalid("unknown PNG compression or filter method"));
+    }
+    if header[12] != 0 {
+        return Err(invalid("interlaced PNG images are not supported"));
+    }
+    let channels = match (colour_type, depth) {
+        (0, 1 | 2 | 4 | 8 | 16) | (3, 1 | 2 | 4 | 8) => 1,
+        (4, 8 | 16) => 2,
+        (2, 8 | 16) => 3,
+        (6, 8 | 16) => 4,
+        _ => return Err(invalid("unknown PNG colour type")),
+    };
+
+    if width == 0 || height == 0 {
+        return Err(invalid("PNG image without pixels"));
+    }
+
+    // bytes of a row and of a whole pixel, at least one, to the left for filtering
+    let stride = width
+        .checked_mul(channels * depth)
+        .ok_or(invalid("PNG image too large"))?
+        .div_ceil(8);
+    let step = (channels * depth).div_ceil(8);
+    let (length, size) = height
+        .checked_mul(stride + 1)
+        .zip(height.checked_mul(stride))
+        .ok_or(invalid("PNG image too large"))?;
+    let data = zlib::decompress(&data)?;
+    if data.len() < length {
+        return Err(invalid("PNG image ends early"));
+    }
+    let mut rows = vec![0u8; size];
+    for (y, line) in data.chunks_exact(stride + 1).take(height).enumerate() {
+        let (filter, line) = (line[0], &line[1..]);
+        for (x, &byte) in line.iter().enumerate() {
+            let index = y * stride + x;
+            let left = if x >= step { rows[index - step] } else { 0 };
+            let up = if y > 0 { rows[index - stride] } else { 0 };
+            let up_left = if x >= step && y > 0 {
+                rows[index - stride - step]
+            } else {
+                0
+            };
+            let predicted = match filter {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((left as u16 + up as u16) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                _ => return Err(invalid("unknown PNG filter")),
+            };
+            rows[index] = byte.wrapping_add(predicted);
+        }
+    }
+
+    // sample `index` of a row as 8 bits, palette indices stay as they are
+    let sample = |row: &[u8], index: usize| -> usize {
+        match depth {
+            8 => row[index] as usize,
+            16 => row[2 * index] as usize,
+            _ => {
+                let bit = index * depth;
+                let value = (row[bit / 8] >> (8 - depth - bit % 8)) as usize & ((1 << depth) - 1);
+                match colour_type {
+                    3 => value,
+                    _ => value * 255 / ((1 << depth) - 1),
+                }
+            }
+        }
+    };
+    let mut pixels = Vec::with_capacity(width * height);
+    for row in rows.chunks_exact(stride) {
+        for x in 0..width {
+            let samples: Vec<usize> = (0..channels)
+                .map(|channel| sample(row, x * channels + channel))
+                .collect();
+            let pixel = match colour_type {
+                0 => [samples[0] as u8; 3],
+                2 => [samples[0] as u8, samples[1] as u8, samples[2] as u8],
+                3 => *palette
+                    .get(samples[0])
+                    .ok_or(invalid("PNG palette index out of range"))?,
+                4 => blend([samples[0] as u8; 3], samples[1]),
+                _ => blend(
+                    [samples[0] as u8, samples[1] as u8, samples[2] as u8],
+                    samples[3],
+                ),
+            };
+            pixels.push(pixel);
+        }
+    }
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Predictor of the Paeth filter, the neighbour closest to `left + up - up_left`.
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = left as i16 + up as i16 - up_left as i16;
+    let distance = |value: u8| (estimate - value as i16).abs();
+    if distance(left) <= distance(up) && distance(left) <= distance(up_left) {
+        left
+    } else if distance(up) <= distance(up_left) {
+        up
+    } else {
+        up_left
+    }
+}
+
+/// Blends `colour` with an opacity of `alpha` out of `255` onto white.
+fn blend(colour: [u8; 3], alpha: usize) -> [u8; 3] {
+    colour.map(|value| ((value as usize * alpha + 255 * (255 - alpha)) / 255) as u8)
+}
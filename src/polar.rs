@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+
+use crate::error::MazeError;
+use crate::search;
+use crate::solve::{Path, Solvable, SolveAlgorithm};
+
+/// Circular maze of rings around a centre cell, the number of cells per ring grows outward.
+///
+/// Positions are `(cell, ring)`, where ring `0` is the single centre cell and cells are
+/// counted clockwise from the top. The start is the centre, the exit is a cell of the outer
+/// ring with an opening to the outside. Create one with
+/// [`PolarBacktracker`](crate::PolarBacktracker).
+#[derive(Clone, Debug)]
+pub struct PolarMaze {
+    /// Number of cells in each ring
+    pub(crate) ring_sizes: Vec<usize>,
+    /// Passages of each cell as `links[ring][cell]`
+    pub(crate) links: Vec<Vec<Links>>,
+    /// Cell of the outer ring with the exit
+    pub(crate) exit: usize,
+    /// Route marked by [`Solvable::solve_with`]
+    pub(crate) path: Path,
+}
+
+/// Open walls of a single [`PolarMaze`] cell, the others are stored with its neighbours.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Links {
+    /// Passage to the next cell of the same ring
+    pub(crate) clockwise: bool,
+    /// Passage to the cell of the next inner ring
+    pub(crate) inward: bool,
+}
+
+impl PolarMaze {
+    /// Creates a [`PolarMaze`] of `rings` rings with all walls closed.
+    pub(crate) fn closed(rings: usize) -> Result<Self, MazeError> {
+        if rings < 2 {
+            return Err(MazeError::TooFewRings { rings });
+        }
+
+        let mut ring_sizes = vec![1];
+        for ring in 1..rings {
+            // keep the cells about as wide as the rings are high
+            let previous = ring_sizes[ring - 1];
+            let ratio = (TAU * ring as f64 / previous as f64).round().max(1.0);
+            ring_sizes.push(previous * ratio as usize);
+        }
+        let links = ring_sizes
+            .iter()
+            .map(|&size| vec![Links::default(); size])
+            .collect();
+
+        Ok(Self {
+            ring_sizes,
+            links,
+            exit: 0,
+            path: Vec::new(),
+        })
+    }
+
+    /// Returns the number of rings including the centre.
+    pub fn rings(&self) -> usize {
+        self.ring_sizes.len()
+    }
+
+    /// Returns the number of cells of `ring`, `None` if there is no such ring.
+    pub fn cells_in_ring(&self, ring: usize) -> Option<usize> {
+        self.ring_sizes.get(ring).copied()
+    }
+
+    /// Returns the starting position, the centre cell.
+    pub fn start(&self) -> (usize, usize) {
+        (0, 0)
+    }
+
+    /// Returns the position of the exit in the outer ring.
+    pub fn exit(&self) -> (usize, usize) {
+        (self.exit, self.rings() - 1)
+    }
+
+    /// Returns the route marked by [`Solvable::solve_with`], empty if not solved yet.
+    pub fn path(&self) -> &[(usize, usize)] {
+        &self.path
+    }
+
+    /// Whether the cells `a` and `b` are neighbours without a wall between them.
+    pub fn is_linked(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        if !self.contains(a) || !self.contains(b) {
+            return false;
+        }
+        if a.1 == b.1 {
+            (b == self.clockwise(a) && self.links[a.1][a.0].clockwise)
+                || (a == self.clockwise(b) && self.links[b.1][b.0].clockwise)
+        } else {
+            let (inner, outer) = if a.1 < b.1 { (a, b) } else { (b, a) };
+            self.parent(outer) == Some(inner) && self.links[outer.1][outer.0].inward
+        }
+    }
+
+    fn contains(&self, (cell, ring): (usize, usize)) -> bool {
+        self.cells_in_ring(ring).is_some_and(|size| cell < size)
+    }
+
+    /// Number of cells of `ring` next to each cell of the ring inside it.
+    fn ratio(&self, ring: usize) -> usize {
+        self.ring_sizes[ring] / self.ring_sizes[ring - 1]
+    }
+
+    fn clockwise(&self, (cell, ring): (usize, usize)) -> (usize, usize) {
+        ((cell + 1) % self.ring_sizes[ring], ring)
+    }
+
+    fn counter_clockwise(&self, (cell, ring): (usize, usize)) -> (usize, usize) {
+        let size = self.ring_sizes[ring];
+        ((cell + size - 1) % size, ring)
+    }
+
+    fn parent(&self, (cell, ring): (usize, usize)) -> Option<(usize, usize)> {
+        (ring > 0).then(|| (cell / self.ratio(ring), ring - 1))
+    }
+
+    /// All cells next to `position` regardless of walls, in the order inward, clockwise,
+    /// counter-clockwise, outward.
+    pub(crate) fn neighbours(&self, position: (usize, usize)) -> Vec<(usize, usize)> {
+        let (cell, ring) = position;
+        let mut neighbours: Vec<(usize, usize)> = self.parent(position).into_iter().collect();
+        if ring > 0 {
+            neighbours.push(self.clockwise(position));
+            neighbours.push(self.counter_clockwise(position));
+        }
+        if ring + 1 < self.rings() {
+            let ratio = self.ratio(ring + 1);
+            neighbours.extend((cell * ratio..(cell + 1) * ratio).map(|next| (next, ring + 1)));
+        }
+        neighbours
+    }
+
+    /// Opens the wall between the neighbouring cells `a` and `b`.
+    pub(crate) fn link(&mut self, a: (usize, usize), b: (usize, usize)) {
+        if a.1 == b.1 {
+            let from = if b == self.clockwise(a) { a } else { b };
+            self.links[from.1][from.0].clockwise = true;
+        } else {
+            let outer = if a.1 > b.1 { a } else { b };
+            self.links[outer.1][outer.0].inward = true;
+        }
+    }
+
+    /// Cells reachable in one step from `position`.
+    fn passages(&self, position: (usize, usize)) -> Vec<(usize, usize)> {
+        self.neighbours(position)
+            .into_iter()
+            .filter(|&next| self.is_linked(position, next))
+            .collect()
+    }
+}
+
+impl Solvable for PolarMaze {
+    fn find_path_with(&self, algorithm: SolveAlgorithm) -> Result<Option<Path>, MazeError> {
+        let exit = self.exit();
+        let is_exit = |position| position == exit;
+        Ok(match algorithm {
+            SolveAlgorithm::DepthFirst => {
+                let passages = |position| self.passages(position);
+                search::depth_first(self.start(), passages, is_exit, HashMap::default())
+            }
+            SolveAlgorithm::BreadthFirst => {
+                let passages = |position| self.passages(position);
+                search::breadth_first(self.start(), passages, is_exit, HashMap::default())
+            }
+            SolveAlgorithm::Dijkstra => {
+                // every cell costs the same
+                let passages = |position| self.passages(position).into_iter().map(|next| (next, 1));
+                search::cheapest(self.start(), passages, is_exit, |_| 0.0, HashMap::default()).0
+            }
+        })
+    }
+
+    fn solve_with(&mut self, algorithm: SolveAlgorithm) -> Result<bool, MazeError> {
+        match self.find_path_with(algorithm)? {
+            Some(path) => {
+                self.path = path;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::cell::{AsChar, FloorType, MazeCell};
+use crate::maze::{Markers, Maze};
+use crate::shape::Shape;
+
+/// Characters used by [`Maze::to_string_with`] and
+/// [`LayeredMaze::to_string_with`](crate::LayeredMaze::to_string_with).
+///
+/// Only [`RenderStyle::Emoji`] shifts the odd rows of a [`Shape::Hex`] maze, the other styles
+/// draw every maze as a square grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RenderStyle {
+    /// Double width emoji of [`AsChar`], as used by `Display`.
+    #[default]
+    Emoji,
+    /// Plain ASCII in the text format of [`Maze::new_from_str_array`], with `.` for the path.
+    ///
+    /// This is the canonical text form: parsing it and rendering it again gives the same text.
+    /// Cells other than plain floor and the path keep their character, a start, exit or path
+    /// on them is listed in the `@` marker lines after the rows. The [`Shape`], movement and
+    /// topology are not part of it.
+    Ascii,
+    /// Walls joined into lines of box-drawing characters, other cells as in
+    /// [`RenderStyle::Ascii`] but with the start and exits drawn over any cell and without
+    /// marker lines.
+    BoxDrawing,
+    /// Two rows per line, walls as `█`, `▀` and `▄`. Cells other than walls and plain floor
+    /// are shown as in [`RenderStyle::Ascii`] and take up both rows, the upper one wins.
+    HalfBlock,
+}
+
+impl Maze {
+    /// Renders this [`Maze`] as text in the given [`RenderStyle`], one line per row.
+    pub fn to_string_with(&self, style: RenderStyle) -> String {
+        let start = Some((self.start_x, self.start_y));
+        let mut s = self.render(start, &self.exits, style);
+        if style == RenderStyle::Ascii {
+            s.push_str(&self.markers(start, &self.exits).write());
+        }
+        s
+    }
+
+    /// Start, exits and path on cells the rows of [`RenderStyle::Ascii`] can't mark, the path
+    /// ordered by rows.
+    pub(crate) fn markers(
+        &self,
+        start: Option<(usize, usize)>,
+        exits: &[(usize, usize)],
+    ) -> Markers<2> {
+        let hidden = |&(x, y): &(usize, usize)| !Maze::can_mark(&self.map[y][x]);
+        let mut path: Vec<[usize; 2]> = self.marked.iter().map(|&(x, y)| [x, y]).collect();
+        path.sort_by_key(|&[x, y]| (y, x));
+        Markers {
+            start: start.filter(hidden).map(|(x, y)| [x, y]),
+            exits: exits
+                .iter()
+                .filter(|&&exit| hidden(&exit) || Some(exit) == start)
+                .map(|&(x, y)| [x, y])
+                .collect(),
+            path,
+        }
+    }
+
+    /// Display characters of the cells with the given start and exits.
+    pub(crate) fn render(
+        &self,
+        start: Option<(usize, usize)>,
+        exits: &[(usize, usize)],
+        style: RenderStyle,
+    ) -> String {
+        // cell at `x`, `y` with the start and exits as markers, in the canonical form only
+        // where they don't hide the cell
+        let cell = |x: usize, y: usize| {
+            let shown = style != RenderStyle::Ascii || Maze::can_mark(&self.map[y][x]);
+            if start == Some((x, y)) && shown {
+                MazeCell::Floor(FloorType::Start)
+            } else if exits.contains(&(x, y)) && shown {
+                MazeCell::Floor(FloorType::Exit)
+            } else {
+                self.map[y][x].clone()
+            }
+        };
+        let mut s = String::new();
+
+        match style {
+            RenderStyle::Emoji => {
+                for y in 0..self.height {
+                    if self.shape == Shape::Hex && y % 2 == 1 {
+                        // shift odd rows by half a (double width) cell
+                        s.push(' ');
+                    }
+                    s.extend((0..self.width).map(|x| cell(x, y).as_char()));
+                    s.push('\n');
+                }
+            }
+            RenderStyle::Ascii | RenderStyle::BoxDrawing => {
+                for y in 0..self.height {
+                    for x in 0..self.width {
+                        s.push(match cell(x, y) {
+                            MazeCell::Wall if style == RenderStyle::BoxDrawing => {
+                                self.box_char(x, y)
+                            }
+                            cell => Maze::input_char(&cell),
+                        });
+                    }
+                    s.push('\n');
+                }
+            }
+            RenderStyle::HalfBlock => {
+                let floor = MazeCell::Floor(FloorType::Floor);
+                for y in (0..self.height).step_by(2) {
+                    for x in 0..self.width {
+                        let upper = cell(x, y);
+                        // a missing last row is empty
+                        let lower = if y + 1 < self.height {
+                            cell(x, y + 1)
+                        } else {
+                            floor.clone()
+                        };
+                        s.push(match (&upper, &lower) {
+                            (MazeCell::Wall, MazeCell::Wall) => '█',
+                            (MazeCell::Wall, lower) if *lower == floor => '▀',
+                            (upper, MazeCell::Wall) if *upper == floor => '▄',
+                            (upper, lower) if *upper == floor && *lower == floor => ' ',
+                            (MazeCell::Floor(_), _) if upper != floor => Maze::input_char(&upper),
+                            _ => Maze::input_char(&lower),
+                        });
+                    }
+                    s.push('\n');
+                }
+            }
+        }
+
+        s
+    }
+
+    /// Box-drawing character of the wall at `x`, `y` joining it with the neighbouring walls.
+    fn box_char(&self, x: usize, y: usize) -> char {
+        let wall = |x: Option<usize>, y: Option<usize>| {
+            matches!(
+                x.zip(y).and_then(|(x, y)| self.map.get(y)?.get(x)),
+                Some(MazeCell::Wall)
+            )
+        };
+        let left = wall(x.checked_sub(1), Some(y));
+        let right = wall(Some(x + 1), Some(y));
+        let up = wall(Some(x), y.checked_sub(1));
+        let down = wall(Some(x), Some(y + 1));
+        match (left, right, up, down) {
+            (false, false, false, false) => '▪',
+            (_, _, false, false) => '─',
+            (false, false, _, _) => '│',
+            (false, true, false, true) => '┌',
+            (true, false, false, true) => '┐',
+            (false, true, true, false) => '└',
+            (true, false, true, false) => '┘',
+            (true, true, false, true) => '┬',
+            (true, true, true, false) => '┴',
+            (false, true, true, true) => '├',
+            (true, false, true, true) => '┤',
+            (true, true, true, true) => '┼',
+        }
+    }
+}
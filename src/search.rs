@@ -0,0 +1,273 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::solve::SearchStats;
+
+/// Entry of an A* open list, ordered so that [`BinaryHeap`] pops the lowest estimate first.
+#[derive(Debug)]
+struct OpenNode<N> {
+    estimate: f64,
+    cost: usize,
+    position: N,
+}
+
+impl<N> PartialEq for OpenNode<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N> Eq for OpenNode<N> {}
+
+impl<N> PartialOrd for OpenNode<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for OpenNode<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // lowest estimate first, on ties prefer the node further along its route
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then(self.cost.cmp(&other.cost))
+    }
+}
+
+/// Value of every node a search has reached.
+pub(crate) trait NodeMap<N, V> {
+    /// Value of `node`, `None` if it was not reached yet.
+    fn get(&self, node: N) -> Option<&V>;
+
+    /// Value of `node` to change, `None` if it was not reached yet.
+    fn get_mut(&mut self, node: N) -> Option<&mut V>;
+
+    /// Stores `value` for `node` if it was not reached yet, returns whether it was stored.
+    fn insert(&mut self, node: N, value: V) -> bool;
+}
+
+impl<N: Eq + Hash, V> NodeMap<N, V> for HashMap<N, V> {
+    fn get(&self, node: N) -> Option<&V> {
+        HashMap::get(self, &node)
+    }
+
+    fn get_mut(&mut self, node: N) -> Option<&mut V> {
+        HashMap::get_mut(self, &node)
+    }
+
+    fn insert(&mut self, node: N, value: V) -> bool {
+        match self.entry(node) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                true
+            }
+            Entry::Occupied(_) => false,
+        }
+    }
+}
+
+/// Values of the positions `(x, y)` of a grid, much faster than hashing for large mazes.
+pub(crate) struct Grid<V> {
+    width: usize,
+    cells: Vec<Option<V>>,
+}
+
+impl<V> Grid<V> {
+    /// Grid of `width` x `height` positions, none of them reached.
+    pub(crate) fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            cells: std::iter::repeat_with(|| None)
+                .take(width * height)
+                .collect(),
+        }
+    }
+}
+
+impl<V> NodeMap<(usize, usize), V> for Grid<V> {
+    fn get(&self, (x, y): (usize, usize)) -> Option<&V> {
+        self.cells[y * self.width + x].as_ref()
+    }
+
+    fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut V> {
+        self.cells[y * self.width + x].as_mut()
+    }
+
+    fn insert(&mut self, (x, y): (usize, usize), value: V) -> bool {
+        let cell = &mut self.cells[y * self.width + x];
+        if cell.is_some() {
+            return false;
+        }
+        *cell = Some(value);
+        true
+    }
+}
+
+/// Cost and previous node of a node reached by [`cheapest`].
+pub(crate) struct Reached<N> {
+    cost: usize,
+    previous: Option<N>,
+    closed: bool,
+}
+
+/// Depth-first search from `start` to the first node matching `is_goal`.
+///
+/// Neighbours are tried in the order `neighbours` returns them, `visited` records the nodes
+/// reached.
+pub(crate) fn depth_first<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(N) -> I,
+    is_goal: impl Fn(N) -> bool,
+    mut visited: impl NodeMap<N, ()>,
+) -> Option<Vec<N>>
+where
+    N: Copy,
+    I: IntoIterator<Item = N>,
+{
+    visited.insert(start, ());
+    // current route from the start, each entry with the index of the next neighbour to try
+    let mut stack = vec![(start, 0)];
+
+    while let Some((node, next)) = stack.last_mut() {
+        if is_goal(*node) {
+            return Some(stack.into_iter().map(|(node, _)| node).collect());
+        }
+        match neighbours(*node).into_iter().nth(*next) {
+            Some(neighbour) => {
+                *next += 1;
+                if visited.insert(neighbour, ()) {
+                    stack.push((neighbour, 0));
+                }
+            }
+            None => {
+                // dead end, backtrack
+                stack.pop();
+            }
+        }
+    }
+
+    None
+}
+
+/// Breadth-first search from `start`, returns a route to a goal with the fewest steps.
+///
+/// `previous` records the previous node on the shortest route to each node reached.
+pub(crate) fn breadth_first<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(N) -> I,
+    is_goal: impl Fn(N) -> bool,
+    mut previous: impl NodeMap<N, Option<N>>,
+) -> Option<Vec<N>>
+where
+    N: Copy,
+    I: IntoIterator<Item = N>,
+{
+    previous.insert(start, None);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(node) {
+            return Some(trace_back(node, |node| {
+                previous.get(node).copied().flatten()
+            }));
+        }
+        for next in neighbours(node) {
+            if previous.insert(next, Some(node)) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// A* search from `start`, returns the cheapest route to a goal and the [`SearchStats`].
+///
+/// `neighbours` returns the next nodes with the cost of stepping onto them, `estimate` must
+/// never overestimate the remaining cost for the route to be the cheapest one. `reached`
+/// records the cost and previous node of each node reached.
+pub(crate) fn cheapest<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(N) -> I,
+    is_goal: impl Fn(N) -> bool,
+    estimate: impl Fn(N) -> f64,
+    mut reached: impl NodeMap<N, Reached<N>>,
+) -> (Option<Vec<N>>, SearchStats)
+where
+    N: Copy,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    reached.insert(
+        start,
+        Reached {
+            cost: 0,
+            previous: None,
+            closed: false,
+        },
+    );
+    let mut open = BinaryHeap::from([OpenNode {
+        estimate: estimate(start),
+        cost: 0,
+        position: start,
+    }]);
+    let mut stats = SearchStats::default();
+
+    while let Some(OpenNode { cost, position, .. }) = open.pop() {
+        match reached.get_mut(position) {
+            // stale entry, node was reached cheaper before
+            Some(node) if node.closed => continue,
+            Some(node) => node.closed = true,
+            None => {}
+        }
+        stats.nodes_expanded += 1;
+
+        if is_goal(position) {
+            stats.path_cost = Some(cost);
+            let path = trace_back(position, |node| reached.get(node)?.previous);
+            return (Some(path), stats);
+        }
+
+        for (next, step_cost) in neighbours(position) {
+            let next_cost = cost + step_cost;
+            match reached.get_mut(next) {
+                Some(node) if node.closed || node.cost <= next_cost => continue,
+                Some(node) => {
+                    node.cost = next_cost;
+                    node.previous = Some(position);
+                }
+                None => {
+                    reached.insert(
+                        next,
+                        Reached {
+                            cost: next_cost,
+                            previous: Some(position),
+                            closed: false,
+                        },
+                    );
+                }
+            }
+            open.push(OpenNode {
+                estimate: next_cost as f64 + estimate(next),
+                cost: next_cost,
+                position: next,
+            });
+        }
+    }
+
+    (None, stats)
+}
+
+/// Follows `previous` from `end` back to the start and returns the route from the start.
+fn trace_back<N: Copy>(end: N, previous: impl Fn(N) -> Option<N>) -> Vec<N> {
+    let mut path = Vec::new();
+    let mut current = Some(end);
+    while let Some(node) = current {
+        path.push(node);
+        current = previous(node);
+    }
+    path.reverse();
+    path
+}
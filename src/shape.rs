@@ -0,0 +1,50 @@
+/// Shape of the cells of a [`Maze`](crate::Maze).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Shape {
+    /// Square cells with up to 4 neighbours, or 8 with diagonal
+    /// [`Movement`](crate::Movement).
+    #[default]
+    Square,
+    /// Pointy-top hexagonal cells with 6 neighbours, the [`Movement`](crate::Movement) is
+    /// ignored.
+    ///
+    /// Positions are offset coordinates where every odd row is shifted right by half a cell, so
+    /// the text format stays one character per cell.
+    Hex,
+}
+
+/// How the edges of a [`Maze`](crate::Maze) are connected.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Topology {
+    /// The maze ends at its edges and, without explicit exits, the border is the exit.
+    #[default]
+    Bounded,
+    /// Opposite edges are connected, leaving the maze on one side enters it on the other.
+    ///
+    /// A torus has no border, so exits must be given explicitly. Hex mazes need an even height
+    /// for the shifted rows to line up, the solvers reject odd ones with
+    /// [`MazeError::OddHexTorus`](crate::MazeError::OddHexTorus).
+    Torus,
+}
+
+/// Neighbours of the hexagon at `x`, `y` in the order left, right, up-left, up-right, down-left,
+/// down-right.
+///
+/// Positions left of or above the grid wrap around to `usize::MAX`.
+pub(crate) fn hex_neighbours(x: usize, y: usize) -> [(usize, usize); 6] {
+    let (up, down) = (y.wrapping_sub(1), y + 1);
+    // odd rows are shifted right, so their upper and lower neighbours are too
+    let (left, right) = if y.is_multiple_of(2) {
+        (x.wrapping_sub(1), x)
+    } else {
+        (x, x + 1)
+    };
+    [
+        (x.wrapping_sub(1), y),
+        (x + 1, y),
+        (left, up),
+        (right, up),
+        (left, down),
+        (right, down),
+    ]
+}
@@ -0,0 +1,335 @@
+use crate::cell::{FloorType, MazeCell};
+use crate::error::MazeError;
+use crate::maze::Maze;
+use crate::movement::Movement;
+use crate::search::{self, Grid};
+
+/// Route through a [`Maze`] as coordinates `(x, y)`, starting with the start position.
+pub type Path = Vec<(usize, usize)>;
+
+/// Search strategy used by [`Solvable::find_path_with`] and [`Solvable::solve_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SolveAlgorithm {
+    /// Depth-first search, returns the first route found.
+    #[default]
+    DepthFirst,
+    /// Breadth-first search, returns a route with the minimum number of steps.
+    BreadthFirst,
+    /// Dijkstra's algorithm, returns the route with the lowest total
+    /// [`FloorType::cost`](crate::FloorType::cost).
+    Dijkstra,
+}
+
+/// Distance estimate used to guide [`Maze::find_path_astar`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Heuristic {
+    /// Sum of the horizontal and vertical distance.
+    #[default]
+    Manhattan,
+    /// Larger of the horizontal and vertical distance.
+    Chebyshev,
+    /// Straight-line distance.
+    Euclidean,
+    /// Always `0`, turns A* into Dijkstra's algorithm.
+    Zero,
+}
+
+impl Heuristic {
+    /// Estimated number of steps from `from` to `to`.
+    ///
+    /// Solvers multiply it with the cheapest [`FloorType::cost`](crate::FloorType::cost) in
+    /// the maze. With diagonal [`Movement`](crate::Movement) or [`Shape::Hex`](crate::Shape::Hex)
+    /// only [`Heuristic::Chebyshev`] and [`Heuristic::Zero`] never overestimate, so only these
+    /// guarantee the cheapest route.
+    pub fn estimate(&self, from: (usize, usize), to: (usize, usize)) -> f64 {
+        self.estimate_distance((from.0.abs_diff(to.0), from.1.abs_diff(to.1)))
+    }
+
+    /// Estimated number of steps for a horizontal and vertical distance.
+    pub(crate) fn estimate_distance(&self, (dx, dy): (usize, usize)) -> f64 {
+        let (dx, dy) = (dx as f64, dy as f64);
+        match self {
+            Heuristic::Manhattan => dx + dy,
+            Heuristic::Chebyshev => dx.max(dy),
+            Heuristic::Euclidean => (dx * dx + dy * dy).sqrt(),
+            Heuristic::Zero => 0.0,
+        }
+    }
+}
+
+/// Statistics of a [`Maze::find_path_astar`] run.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchStats {
+    /// Number of cells taken from the open list and expanded.
+    pub nodes_expanded: usize,
+    /// Total [`FloorType::cost`](crate::FloorType::cost) of the route found, `None` if no goal
+    /// is reachable.
+    pub path_cost: Option<usize>,
+}
+
+/// Mazes that can search a route from their start to an exit.
+pub trait Solvable {
+    /// Finds a route from the start to an exit without modifying `self`.
+    ///
+    /// Returns the route, or `None` if there is no solution.
+    fn find_path_with(&self, algorithm: SolveAlgorithm) -> Result<Option<Path>, MazeError>;
+
+    /// Finds a route with the default [`SolveAlgorithm`].
+    fn find_path(&self) -> Result<Option<Path>, MazeError> {
+        self.find_path_with(SolveAlgorithm::default())
+    }
+
+    /// Finds a route like [`Solvable::find_path_with`] and marks it in `self`.
+    fn solve_with(&mut self, algorithm: SolveAlgorithm) -> Result<bool, MazeError>;
+
+    /// Solves `self` with the default [`SolveAlgorithm`] and marks the route found.
+    fn solve(&mut self) -> Result<bool, MazeError> {
+        self.solve_with(SolveAlgorithm::default())
+    }
+}
+
+impl Maze {
+    fn find_path_depth_first(&self) -> Result<Option<Path>, MazeError> {
+        self.validate_start_in_bounds()?;
+        let start = (self.start_x, self.start_y);
+        if self.map[start.1][start.0] == MazeCell::Wall {
+            return Ok(None);
+        }
+        Ok(search::depth_first(
+            start,
+            |(x, y)| self.neighbours(x, y),
+            |(x, y)| self.is_exit(x, y),
+            Grid::new(self.width, self.height),
+        ))
+    }
+
+    fn find_path_breadth_first(&self) -> Result<Option<Path>, MazeError> {
+        self.validate_start_in_bounds()?;
+        Ok(search::breadth_first(
+            (self.start_x, self.start_y),
+            |(x, y)| self.neighbours(x, y),
+            |(x, y)| self.is_exit(x, y),
+            Grid::new(self.width, self.height),
+        ))
+    }
+
+    /// Finds a route with A*, targeting the given goal cells instead of the border.
+    ///
+    /// Returns the route from the start (or `None` if no goal is reachable) together with
+    /// the [`SearchStats`] of the search.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if no goals are given, a goal is out of bounds or on
+    /// a wall, or the maze is a hex torus of odd height.
+    pub fn find_path_astar(
+        &self,
+        goals: &[(usize, usize)],
+        heuristic: Heuristic,
+    ) -> Result<(Option<Path>, SearchStats), MazeError> {
+        self.validate_topology()?;
+        if goals.is_empty() {
+            return Err(MazeError::NoGoals);
+        }
+        for &(x, y) in goals {
+            match self.map.get(y).and_then(|row| row.get(x)) {
+                None => return Err(MazeError::GoalOutOfBounds { x, y }),
+                Some(MazeCell::Wall) => return Err(MazeError::GoalOnWall { x, y }),
+                Some(MazeCell::Floor(_)) => {}
+            }
+        }
+
+        // scale the estimated steps, so the estimate never exceeds the real cost
+        let step_cost = self
+            .map
+            .iter()
+            .flatten()
+            .filter_map(|cell| match cell {
+                // leaving a conveyor is free, a slide covers any distance for a single step
+                MazeCell::Floor(FloorType::Conveyor(_) | FloorType::Ice) => Some(0),
+                _ if self.movement == Movement::Sliding => Some(0),
+                MazeCell::Floor(floor) => Some(floor.cost()),
+                MazeCell::Wall => None,
+            })
+            .min()
+            .unwrap_or(1) as f64;
+        let nearest = |position: (usize, usize), targets: &mut dyn Iterator<Item = _>| {
+            targets
+                .map(|target| heuristic.estimate_distance(self.distance(position, target)))
+                .fold(f64::INFINITY, f64::min)
+        };
+        // a route through portals walks at least to the nearest portal and from the portal
+        // nearest to a goal, the jumps in between are free
+        let from_portals = goals
+            .iter()
+            .map(|&goal| nearest(goal, &mut self.portals.keys().copied()))
+            .fold(f64::INFINITY, f64::min);
+        let estimate = |position: (usize, usize)| {
+            let direct = nearest(position, &mut goals.iter().copied());
+            let through_portals = nearest(position, &mut self.portals.keys().copied());
+            direct.min(through_portals + from_portals) * step_cost
+        };
+
+        Ok(self.find_cheapest(|position| goals.contains(&position), estimate))
+    }
+
+    /// Finds the route with the lowest total [`FloorType::cost`](crate::FloorType::cost) from
+    /// the start to an exit.
+    ///
+    /// Returns the route together with its cost, or `None` if there is no solution.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the starting position is out of bounds, the maze
+    /// has no border and no explicit exits or it is a hex torus of odd height.
+    pub fn find_cheapest_path(&self) -> Result<Option<(Path, usize)>, MazeError> {
+        self.validate_start_in_bounds()?;
+        self.validate_exits()?;
+        let (path, stats) = self.find_cheapest(|(x, y)| self.is_exit(x, y), |_| 0.0);
+        Ok(path.zip(stats.path_cost))
+    }
+
+    /// Returns all cells reachable from the start with the [`Movement`](crate::Movement) and
+    /// [`Topology`](crate::Topology) of this maze, including the start itself, ordered by rows.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the starting position is out of bounds or the
+    /// maze is a hex torus of odd height.
+    pub fn reachable_cells(&self) -> Result<Vec<(usize, usize)>, MazeError> {
+        self.validate_start_in_bounds()?;
+        self.validate_topology()?;
+
+        let mut visited = vec![vec![false; self.width]; self.height];
+        let mut stack = vec![(self.start_x, self.start_y)];
+        visited[self.start_y][self.start_x] = true;
+        while let Some((x, y)) = stack.pop() {
+            for (next_x, next_y) in self.neighbours(x, y) {
+                if !visited[next_y][next_x] {
+                    visited[next_y][next_x] = true;
+                    stack.push((next_x, next_y));
+                }
+            }
+        }
+
+        Ok((0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| visited[y][x])
+            .collect())
+    }
+
+    /// Returns the cells reachable from the start from which no exit can be reached anymore,
+    /// ordered by rows.
+    ///
+    /// Moves are directed with one-way cells and conveyors, so a walker may enter a part of the
+    /// maze it can never leave. Doors are closed, as for the other solvers of a [`Maze`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the starting position is out of bounds, the maze
+    /// has no border and no explicit exits or it is a hex torus of odd height.
+    pub fn trap_cells(&self) -> Result<Vec<(usize, usize)>, MazeError> {
+        self.validate_exits()?;
+        let reachable = self.reachable_cells()?;
+
+        // every move reversed, to search backwards from the exits
+        let mut previous: Vec<Vec<Vec<(usize, usize)>>> =
+            vec![vec![Vec::new(); self.width]; self.height];
+        let mut stack = Vec::new();
+        for (y, row) in self.map.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if *cell == MazeCell::Wall {
+                    continue;
+                }
+                for (next_x, next_y) in self.neighbours(x, y) {
+                    previous[next_y][next_x].push((x, y));
+                }
+                if self.is_exit(x, y) {
+                    stack.push((x, y));
+                }
+            }
+        }
+        let mut escapes = vec![vec![false; self.width]; self.height];
+        while let Some((x, y)) = stack.pop() {
+            if escapes[y][x] {
+                continue;
+            }
+            escapes[y][x] = true;
+            stack.extend(&previous[y][x]);
+        }
+
+        Ok(reachable
+            .into_iter()
+            .filter(|&(x, y)| !escapes[y][x])
+            .collect())
+    }
+
+    pub(crate) fn validate_start_in_bounds(&self) -> Result<(), MazeError> {
+        if self
+            .map
+            .get(self.start_y)
+            .and_then(|row| row.get(self.start_x))
+            .is_none()
+        {
+            return Err(MazeError::StartOutOfBounds {
+                x: self.start_x,
+                y: self.start_y,
+            });
+        }
+        Ok(())
+    }
+
+    /// A* search from the start to the first cell matching `is_goal`, guided by `estimate`.
+    fn find_cheapest(
+        &self,
+        is_goal: impl Fn((usize, usize)) -> bool,
+        estimate: impl Fn((usize, usize)) -> f64,
+    ) -> (Option<Path>, SearchStats) {
+        let neighbours = |(x, y)| {
+            self.neighbours(x, y)
+                .map(move |next| (next, self.move_cost((x, y), next)))
+        };
+        let reached = Grid::new(self.width, self.height);
+        search::cheapest(
+            (self.start_x, self.start_y),
+            neighbours,
+            is_goal,
+            estimate,
+            reached,
+        )
+    }
+
+    /// Solves this [`Maze`] with A* like [`Maze::find_path_astar`] and marks the route found.
+    pub fn solve_astar(
+        &mut self,
+        goals: &[(usize, usize)],
+        heuristic: Heuristic,
+    ) -> Result<SearchStats, MazeError> {
+        let (path, stats) = self.find_path_astar(goals, heuristic)?;
+        if let Some(path) = path {
+            self.mark_path(&path)?;
+        }
+        Ok(stats)
+    }
+}
+
+impl Solvable for Maze {
+    fn find_path_with(&self, algorithm: SolveAlgorithm) -> Result<Option<Path>, MazeError> {
+        self.validate_exits()?;
+        match algorithm {
+            SolveAlgorithm::DepthFirst => self.find_path_depth_first(),
+            SolveAlgorithm::BreadthFirst => self.find_path_breadth_first(),
+            SolveAlgorithm::Dijkstra => Ok(self.find_cheapest_path()?.map(|(path, _)| path)),
+        }
+    }
+
+    fn solve_with(&mut self, algorithm: SolveAlgorithm) -> Result<bool, MazeError> {
+        match self.find_path_with(algorithm)? {
+            Some(path) => {
+                self.mark_path(&path)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
@@ -0,0 +1,361 @@
+use std::f64::consts::TAU;
+use std::fmt::Write;
+
+use crate::cell::{FloorType, MazeCell};
+use crate::maze::Maze;
+use crate::polar::PolarMaze;
+use crate::shape::{Shape, Topology};
+
+/// Appearance of the SVG images of [`Maze::to_svg_with`] and [`PolarMaze::to_svg_with`].
+///
+/// Sizes are in SVG user units, colours are anything SVG accepts as a fill, like `#ff9800` or
+/// `orange`. Colours are XML-escaped, so no value can break out of its attribute. Terrain and
+/// the other special cells keep their own colours.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgStyle {
+    cell_size: f64,
+    margin: f64,
+    wall_width: f64,
+    path_width: f64,
+    wall: String,
+    floor: String,
+    start: String,
+    exit: String,
+    path: String,
+}
+
+impl Default for SvgStyle {
+    fn default() -> Self {
+        let colour = |floor| hex(MazeCell::Floor(floor).colour());
+        Self {
+            cell_size: 20.0,
+            margin: 0.0,
+            wall_width: 2.0,
+            path_width: 3.0,
+            wall: hex(MazeCell::Wall.colour()),
+            floor: colour(FloorType::Floor),
+            start: colour(FloorType::Start),
+            exit: colour(FloorType::Exit),
+            path: colour(FloorType::Path),
+        }
+    }
+}
+
+impl SvgStyle {
+    /// Sets the width of a cell, or of a ring of a [`PolarMaze`].
+    pub fn with_cell_size(mut self, cell_size: f64) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Sets the empty space around the maze.
+    pub fn with_margin(mut self, margin: f64) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets the thickness of the wall lines of a [`PolarMaze`], the walls of a [`Maze`] are
+    /// whole cells.
+    pub fn with_wall_width(mut self, wall_width: f64) -> Self {
+        self.wall_width = wall_width;
+        self
+    }
+
+    /// Sets the thickness of the line along the solution.
+    pub fn with_path_width(mut self, path_width: f64) -> Self {
+        self.path_width = path_width;
+        self
+    }
+
+    /// Sets the colour of the walls.
+    pub fn with_wall_colour(mut self, colour: &str) -> Self {
+        self.wall = escape(colour);
+        self
+    }
+
+    /// Sets the colour of plain floor.
+    pub fn with_floor_colour(mut self, colour: &str) -> Self {
+        self.floor = escape(colour);
+        self
+    }
+
+    /// Sets the colour of the start.
+    pub fn with_start_colour(mut self, colour: &str) -> Self {
+        self.start = escape(colour);
+        self
+    }
+
+    /// Sets the colour of the exits.
+    pub fn with_exit_colour(mut self, colour: &str) -> Self {
+        self.exit = escape(colour);
+        self
+    }
+
+    /// Sets the colour of the line along the solution and of [`FloorType::Path`] cells.
+    pub fn with_path_colour(mut self, colour: &str) -> Self {
+        self.path = escape(colour);
+        self
+    }
+
+    /// Fill colour of a cell.
+    fn fill(&self, cell: &MazeCell) -> String {
+        match cell {
+            MazeCell::Wall => self.wall.clone(),
+            MazeCell::Floor(FloorType::Floor) => self.floor.clone(),
+            MazeCell::Floor(FloorType::Start) => self.start.clone(),
+            MazeCell::Floor(FloorType::Exit) => self.exit.clone(),
+            MazeCell::Floor(FloorType::Path) => self.path.clone(),
+            MazeCell::Floor(_) => hex(cell.colour()),
+        }
+    }
+}
+
+impl Maze {
+    /// Renders this [`Maze`] as an SVG image in the default [`SvgStyle`], without a solution.
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with(&SvgStyle::default(), &[])
+    }
+
+    /// Renders this [`Maze`] as an SVG image, one filled square or hexagon per cell and `path`
+    /// as a line through the centres of its cells.
+    ///
+    /// Hexagons are drawn pointy-top with every odd row shifted right by half a cell, matching
+    /// the offset coordinates of [`Shape::Hex`]. The line is interrupted where `path` jumps
+    /// through a portal or across the edges of a [`Topology::Torus`].
+    pub fn to_svg_with(&self, style: &SvgStyle, path: &[(usize, usize)]) -> String {
+        let (cell_size, margin) = (style.cell_size, style.margin);
+        // circumradius of a hexagon with the width of a cell
+        let radius = cell_size / 3f64.sqrt();
+        let (width, height) = match self.shape {
+            Shape::Square => (
+                cell_size * self.width as f64,
+                cell_size * self.height as f64,
+            ),
+            Shape::Hex => (
+                cell_size * (self.width as f64 + 0.5),
+                radius * (1.5 * self.height as f64 + 0.5),
+            ),
+        };
+        let (width, height) = (width + 2.0 * margin, height + 2.0 * margin);
+        let center = |(x, y): (usize, usize)| match self.shape {
+            Shape::Square => (
+                margin + cell_size * (x as f64 + 0.5),
+                margin + cell_size * (y as f64 + 0.5),
+            ),
+            Shape::Hex => (
+                margin + cell_size * (x as f64 + 0.5 + 0.5 * (y % 2) as f64),
+                margin + radius * (1.0 + 1.5 * y as f64),
+            ),
+        };
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.2}\" height=\"{height:.2}\" \
+             viewBox=\"0 0 {width:.2} {height:.2}\">\n"
+        );
+        for (y, row) in self.map.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let fill = if (x, y) == (self.start_x, self.start_y) {
+                    style.fill(&MazeCell::Floor(FloorType::Start))
+                } else if self.exits.contains(&(x, y)) {
+                    style.fill(&MazeCell::Floor(FloorType::Exit))
+                } else {
+                    style.fill(cell)
+                };
+
+                // writing to a String never fails
+                let _ = match self.shape {
+                    Shape::Square => writeln!(
+                        svg,
+                        "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{cell_size:.2}\" \
+                         height=\"{cell_size:.2}\" fill=\"{fill}\"/>",
+                        margin + cell_size * x as f64,
+                        margin + cell_size * y as f64,
+                    ),
+                    Shape::Hex => {
+                        let (center_x, center_y) = center((x, y));
+                        let points: Vec<String> = (0..6)
+                            .map(|corner| {
+                                let angle = (60.0 * corner as f64 - 90.0).to_radians();
+                                format!(
+                                    "{:.2},{:.2}",
+                                    round(center_x + radius * angle.cos()),
+                                    round(center_y + radius * angle.sin())
+                                )
+                            })
+                            .collect();
+                        writeln!(
+                            svg,
+                            "<polygon points=\"{}\" fill=\"{fill}\"/>",
+                            points.join(" ")
+                        )
+                    }
+                };
+            }
+        }
+
+        // one line for every part of the path between two jumps
+        let mut lines: Vec<Vec<(usize, usize)>> = Vec::new();
+        for (index, &position) in path.iter().enumerate() {
+            let jumped = index > 0 && {
+                let (x, y) = path[index - 1];
+                let wrapped = self.topology == Topology::Torus
+                    && self.distance((x, y), position)
+                        != (x.abs_diff(position.0), y.abs_diff(position.1));
+                wrapped || self.portal(x, y) == Some(position)
+            };
+            match lines.last_mut() {
+                Some(line) if !jumped => line.push(position),
+                _ => lines.push(vec![position]),
+            }
+        }
+        for line in lines.iter().filter(|line| line.len() > 1) {
+            let points: Vec<String> = line
+                .iter()
+                .map(|&position| {
+                    let (x, y) = center(position);
+                    format!("{:.2},{:.2}", round(x), round(y))
+                })
+                .collect();
+            let _ = writeln!(
+                svg,
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" \
+                 stroke-linecap=\"round\" stroke-linejoin=\"round\"/>",
+                points.join(" "),
+                style.path,
+                style.path_width
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+impl PolarMaze {
+    /// Renders this [`PolarMaze`] as an SVG image in the default [`SvgStyle`].
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with(&SvgStyle::default())
+    }
+
+    /// Renders this [`PolarMaze`] as an SVG image of its walls, with the route marked by
+    /// [`Solvable::solve_with`](crate::Solvable::solve_with) as a line from the centre.
+    pub fn to_svg_with(&self, style: &SvgStyle) -> String {
+        let rings = self.rings();
+        let cell_size = style.cell_size;
+        let center = style.margin + cell_size * (rings as f64 + 0.5);
+        // point at `radius` rings from the centre and `turn` full turns clockwise from the top
+        let point = |radius: f64, turn: f64| {
+            let angle = TAU * turn - TAU / 4.0;
+            (
+                round(center + cell_size * radius * angle.cos()),
+                round(center + cell_size * radius * angle.sin()),
+            )
+        };
+        // centre of a cell
+        let cell_center = |(cell, ring): (usize, usize)| match ring {
+            0 => (center, center),
+            _ => point(
+                ring as f64 + 0.5,
+                (cell as f64 + 0.5) / self.ring_sizes[ring] as f64,
+            ),
+        };
+        // SVG path segment of a clockwise arc of `ring` rings radius from `from` to `to`
+        let arc = |(x1, y1): (f64, f64), (x2, y2): (f64, f64), ring: usize| {
+            let radius = cell_size * ring as f64;
+            format!("M {x1:.2} {y1:.2} A {radius:.2} {radius:.2} 0 0 1 {x2:.2} {y2:.2} ")
+        };
+
+        let mut walls = String::new();
+        for ring in 1..rings {
+            let size = self.ring_sizes[ring] as f64;
+            for (cell, links) in self.links[ring].iter().enumerate() {
+                let (from, to) = (cell as f64 / size, (cell + 1) as f64 / size);
+                if !links.inward {
+                    walls.push_str(&arc(point(ring as f64, from), point(ring as f64, to), ring));
+                }
+                if !links.clockwise {
+                    let (x1, y1) = point(ring as f64, to);
+                    let (x2, y2) = point(ring as f64 + 1.0, to);
+                    walls.push_str(&format!("M {x1:.2} {y1:.2} L {x2:.2} {y2:.2} "));
+                }
+            }
+        }
+        // outer border with the opening of the exit
+        let size = self.ring_sizes[rings - 1] as f64;
+        for cell in (0..self.ring_sizes[rings - 1]).filter(|&cell| cell != self.exit) {
+            let (from, to) = (cell as f64 / size, (cell + 1) as f64 / size);
+            walls.push_str(&arc(
+                point(rings as f64, from),
+                point(rings as f64, to),
+                rings,
+            ));
+        }
+
+        let image_size = 2.0 * center;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{image_size:.2}\" \
+             height=\"{image_size:.2}\" viewBox=\"0 0 {image_size:.2} {image_size:.2}\">\n"
+        );
+        // writing to a String never fails
+        let _ = writeln!(
+            svg,
+            "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" \
+             stroke-linecap=\"round\"/>",
+            walls.trim_end(),
+            style.wall,
+            style.wall_width
+        );
+        if !self.path.is_empty() {
+            let mut points: Vec<(f64, f64)> =
+                self.path.iter().map(|&cell| cell_center(cell)).collect();
+            // leave through the exit
+            points.push(point(rings as f64 + 0.5, (self.exit as f64 + 0.5) / size));
+            let points: Vec<String> = points
+                .iter()
+                .map(|(x, y)| format!("{x:.2},{y:.2}"))
+                .collect();
+            let _ = writeln!(
+                svg,
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>",
+                points.join(" "),
+                style.path,
+                style.path_width
+            );
+        }
+        for (position, colour) in [(self.start(), &style.start), (self.exit(), &style.exit)] {
+            let (x, y) = cell_center(position);
+            let _ = writeln!(
+                svg,
+                "<circle cx=\"{x:.2}\" cy=\"{y:.2}\" r=\"{:.2}\" fill=\"{colour}\"/>",
+                cell_size / 4.0
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Formats a colour as `#rrggbb`.
+fn hex([red, green, blue]: [u8; 3]) -> String {
+    format!("#{red:02x}{green:02x}{blue:02x}")
+}
+
+/// Escapes the characters of `text` that are special in XML attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Rounds `value` to the printed precision, avoiding a printed `-0.00`.
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0 + 0.0
+}
@@ -0,0 +1,350 @@
+use crate::error::MazeError;
+
+/// Shortest lengths of the deflate length codes `257` to `285`.
+const LENGTH_BASE: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+/// Extra bits of the deflate length codes `257` to `285`.
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+/// Shortest distances of the deflate distance codes `0` to `29`.
+const DISTANCE_BASE: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+/// Extra bits of the deflate distance codes `0` to `29`.
+const DISTANCE_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// Longest match deflate can refer to.
+const MAX_LENGTH: usize = 258;
+/// Farthest back deflate can refer to.
+const MAX_DISTANCE: usize = 32768;
+
+/// Adler-32 checksum of zlib streams.
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// Compresses `data` into a zlib stream.
+///
+/// Images of mazes repeat the previous pixel or the row above, `stride` bytes back, so these
+/// are the only matches searched for.
+pub(crate) fn compress(data: &[u8], stride: usize) -> Vec<u8> {
+    // deflate with a 32K window, no dictionary
+    let mut bits = BitWriter {
+        bytes: vec![0x78, 0x01],
+        buffer: 0,
+        count: 0,
+    };
+    // one final block with the fixed Huffman codes
+    bits.write(1, 1);
+    bits.write(1, 2);
+
+    let mut position = 0;
+    while position < data.len() {
+        let (length, distance) = [stride, 3]
+            .into_iter()
+            .filter(|&distance| distance <= position && distance <= MAX_DISTANCE)
+            .map(|distance| {
+                let length = (position..data.len().min(position + MAX_LENGTH))
+                    .take_while(|&index| data[index] == data[index - distance])
+                    .count();
+                (length, distance)
+            })
+            .max()
+            .unwrap_or((0, 0));
+        if length >= LENGTH_BASE[0] {
+            bits.length(length);
+            bits.distance(distance);
+            position += length;
+        } else {
+            bits.symbol(data[position] as u16);
+            position += 1;
+        }
+    }
+    // end of block
+    bits.symbol(256);
+
+    let mut stream = bits.finish();
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+/// Writer of the bit stream of deflate, least significant bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitWriter {
+    /// Writes the lowest `count` bits of `value`.
+    fn write(&mut self, value: u32, count: u32) {
+        self.buffer |= value << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Writes a Huffman code of `length` bits, which starts with its most significant bit.
+    fn code(&mut self, code: u32, length: u32) {
+        self.write(code.reverse_bits() >> (32 - length), length);
+    }
+
+    /// Writes a literal/length symbol with its fixed Huffman code.
+    fn symbol(&mut self, symbol: u16) {
+        let symbol = symbol as u32;
+        match symbol {
+            0..=143 => self.code(0x30 + symbol, 8),
+            144..=255 => self.code(0x190 + symbol - 144, 9),
+            256..=279 => self.code(symbol - 256, 7),
+            _ => self.code(0xc0 + symbol - 280, 8),
+        }
+    }
+
+    /// Writes the length of a match, `3` to `258`.
+    fn length(&mut self, length: usize) {
+        let index = LENGTH_BASE.partition_point(|&base| base <= length) - 1;
+        self.symbol(257 + index as u16);
+        self.write((length - LENGTH_BASE[index]) as u32, LENGTH_EXTRA[index]);
+    }
+
+    /// Writes the distance of a match, `1` to `32768`.
+    fn distance(&mut self, distance: usize) {
+        let index = DISTANCE_BASE.partition_point(|&base| base <= distance) - 1;
+        self.code(index as u32, 5);
+        self.write(
+            (distance - DISTANCE_BASE[index]) as u32,
+            DISTANCE_EXTRA[index],
+        );
+    }
+
+    /// Pads the last byte with zeros and returns the written bytes.
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Order in which the code lengths of the code length alphabet are stored.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Decompresses a zlib stream.
+///
+/// # Errors
+///
+/// This function will return an error if `data` is not a complete zlib stream or its checksum
+/// does not match.
+pub(crate) fn decompress(data: &[u8]) -> Result<Vec<u8>, MazeError> {
+    let invalid = |reason| MazeError::InvalidImage { reason };
+    match data {
+        [method, flags, ..]
+            if method & 0x0f == 8 && (*method as u16 * 256 + *flags as u16).is_multiple_of(31) =>
+        {
+            if flags & 0x20 != 0 {
+                return Err(invalid("compressed data needs a preset dictionary"));
+            }
+        }
+        _ => return Err(invalid("unknown compression")),
+    }
+
+    let mut bits = BitReader {
+        data: &data[2..],
+        position: 0,
+    };
+    let mut output = Vec::new();
+    loop {
+        let last = bits.read(1)? == 1;
+        match bits.read(2)? {
+            0 => {
+                // stored block, its length and the complement are aligned to bytes
+                bits.position = bits.position.div_ceil(8) * 8;
+                let length = bits.read(16)?;
+                if bits.read(16)? != !length & 0xffff {
+                    return Err(damaged());
+                }
+                for _ in 0..length {
+                    output.push(bits.read(8)? as u8);
+                }
+            }
+            1 => {
+                let mut lengths = [8; 288];
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                inflate_block(
+                    &mut bits,
+                    &mut output,
+                    &Huffman::new(&lengths),
+                    &Huffman::new(&[5; 30]),
+                )?;
+            }
+            2 => {
+                let (literals, distances) = read_codes(&mut bits)?;
+                inflate_block(&mut bits, &mut output, &literals, &distances)?;
+            }
+            _ => return Err(damaged()),
+        }
+        if last {
+            break;
+        }
+    }
+
+    let checksum = bits.position.div_ceil(8);
+    match bits.data.get(checksum..checksum + 4) {
+        Some(checksum) if checksum == adler32(&output).to_be_bytes() => Ok(output),
+        _ => Err(invalid("wrong checksum of compressed data")),
+    }
+}
+
+/// Reads the Huffman codes of a block with dynamic codes.
+fn read_codes(bits: &mut BitReader) -> Result<(Huffman, Huffman), MazeError> {
+    let literal_count = bits.read(5)? as usize + 257;
+    let distance_count = bits.read(5)? as usize + 1;
+    let code_length_count = bits.read(4)? as usize + 4;
+
+    let mut code_lengths = [0; 19];
+    for &symbol in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[symbol] = bits.read(3)? as u8;
+    }
+    let code_lengths = Huffman::new(&code_lengths);
+
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let (length, repeat) = match code_lengths.decode(bits)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => match lengths.last() {
+                Some(&previous) => (previous, 3 + bits.read(2)?),
+                None => return Err(damaged()),
+            },
+            17 => (0, 3 + bits.read(3)?),
+            _ => (0, 11 + bits.read(7)?),
+        };
+        lengths.extend((0..repeat).map(|_| length));
+    }
+    if lengths.len() > literal_count + distance_count {
+        return Err(damaged());
+    }
+    Ok((
+        Huffman::new(&lengths[..literal_count]),
+        Huffman::new(&lengths[literal_count..]),
+    ))
+}
+
+/// Decompresses the symbols of a block with Huffman codes until its end.
+fn inflate_block(
+    bits: &mut BitReader,
+    output: &mut Vec<u8>,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Result<(), MazeError> {
+    loop {
+        let symbol = literals.decode(bits)? as usize;
+        match symbol {
+            0..=255 => output.push(symbol as u8),
+            256 => return Ok(()),
+            257..=285 => {
+                let index = symbol - 257;
+                let length = LENGTH_BASE[index] + bits.read(LENGTH_EXTRA[index])? as usize;
+                let index = distances.decode(bits)? as usize;
+                if index >= DISTANCE_BASE.len() {
+                    return Err(damaged());
+                }
+                let distance = DISTANCE_BASE[index] + bits.read(DISTANCE_EXTRA[index])? as usize;
+                if distance > output.len() {
+                    return Err(damaged());
+                }
+                // the match may overlap the bytes it produces
+                let start = output.len() - distance;
+                for index in start..start + length {
+                    output.push(output[index]);
+                }
+            }
+            _ => return Err(damaged()),
+        }
+    }
+}
+
+/// Error of a stream that cannot be decompressed.
+fn damaged() -> MazeError {
+    MazeError::InvalidImage {
+        reason: "damaged compressed data",
+    }
+}
+
+/// Reader of the bit stream of deflate, least significant bit first.
+struct BitReader<'a> {
+    data: &'a [u8],
+    /// Position in bits.
+    position: usize,
+}
+
+impl BitReader<'_> {
+    /// Reads `count` bits, the first one is the least significant.
+    fn read(&mut self, count: u32) -> Result<u32, MazeError> {
+        let mut value = 0;
+        for bit in 0..count {
+            let byte = self.data.get(self.position / 8).ok_or_else(damaged)?;
+            value |= ((*byte as u32 >> (self.position % 8)) & 1) << bit;
+            self.position += 1;
+        }
+        Ok(value)
+    }
+}
+
+/// Canonical Huffman code, decoded one bit at a time.
+struct Huffman {
+    /// Number of codes of every length up to 15 bits.
+    counts: [u16; 16],
+    /// Symbols ordered by their codes.
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// Creates the code from the code length of every symbol, `0` for unused symbols.
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0; 16];
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut symbols: Vec<u16> = (0..lengths.len() as u16)
+            .filter(|&symbol| lengths[symbol as usize] > 0)
+            .collect();
+        // stable, so symbols of the same length stay in order
+        symbols.sort_by_key(|&symbol| lengths[symbol as usize]);
+        Self { counts, symbols }
+    }
+
+    /// Reads the next symbol.
+    fn decode(&self, bits: &mut BitReader) -> Result<u16, MazeError> {
+        // first code and index of the first symbol of the current length
+        let (mut code, mut first, mut index) = (0, 0, 0);
+        for &count in &self.counts[1..] {
+            code |= bits.read(1)? as usize;
+            let count = count as usize;
+            if code < first + count {
+                return Ok(self.symbols[index + code - first]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(damaged())
+    }
+}
@@ -0,0 +1,308 @@
+use std::fs::File;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_maze"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Error while starting maze binary");
+    // the binary may exit before reading its input, so a broken pipe is fine here
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn solved_maze_exits_with_zero() {
+    let output = run(
+        &["--start", "1,1", "--solver", "bfs"],
+        "XXXXX\nX   X\nX X  \nXXXXX\n",
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "⬜⬜⬜⬜⬜\n⬜❌👣👣⬜\n⬜⬛⬜👣👣\n⬜⬜⬜⬜⬜\n"
+    );
+}
+
+#[test]
+fn astar_uses_goals() {
+    let output = run(
+        &[
+            "-s",
+            "1,1",
+            "-a",
+            "astar",
+            "-g",
+            "3,2",
+            "--heuristic",
+            "zero",
+            "-",
+        ],
+        "XXXXX\nX   X\nX X  \nXXXXX\n",
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "⬜⬜⬜⬜⬜\n⬜❌👣👣⬜\n⬜⬛⬜👣⬛\n⬜⬜⬜⬜⬜\n"
+    );
+}
+
+#[test]
+fn unsolvable_maze_exits_with_one() {
+    let output = run(&["-s", "1,1"], "XXX\nX X\nXXX\n");
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn invalid_input_exits_with_two() {
+    assert_eq!(
+        run(&["-s", "1,1"], "XXX\nX?X\nXXX\n").status.code(),
+        Some(2)
+    );
+    assert_eq!(
+        run(&["-s", "0,0"], "XXX\nX X\nXXX\n").status.code(),
+        Some(2)
+    );
+    assert_eq!(run(&[], "XXX\nX X\nXXX\n").status.code(), Some(2));
+    assert_eq!(run(&[], "XXX\nXSS\nXXX\n").status.code(), Some(2));
+    assert_eq!(run(&["-s", "1"], "XXX\nX X\nXXX\n").status.code(), Some(2));
+    assert_eq!(
+        run(&["-s", "1,1", "missing.txt"], "").status.code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn markers_replace_start_and_goal_options() {
+    let output = run(&["-a", "astar"], "XXXXX\nXS  X\nX XE \nXXXXX\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "⬜⬜⬜⬜⬜\n⬜❌👣👣⬜\n⬜⬛⬜🏁⬛\n⬜⬜⬜⬜⬜\n"
+    );
+}
+
+#[test]
+fn movement_option() {
+    let input = "XXXX\nXS X\nXXEX\nXXXX\n";
+
+    let output = run(
+        &["-a", "bfs", "-m", "diagonal", "--corners", "one-wall"],
+        input,
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "⬜⬜⬜⬜\n⬜❌⬛⬜\n⬜⬜🏁⬜\n⬜⬜⬜⬜\n"
+    );
+
+    let output = run(&["-m", "sideways"], input);
+    assert_eq!(output.status.code(), Some(2));
+
+    // corner rules only apply to diagonal steps
+    let output = run(&["-a", "bfs", "--corners", "always"], input);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("--corners needs diagonal movement"));
+    let output = run(&["-m", "sliding", "--corners", "never"], input);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn astar_options_need_the_astar_solver() {
+    let input = "XXXXX\nXS  X\nX XE \nXXXXX\n";
+    for args in [
+        &["-g", "3,2"][..],
+        &["-a", "bfs", "-g", "3,2"],
+        &["--heuristic", "zero"],
+        &["-a", "keys", "--heuristic", "euclidean"],
+    ] {
+        let output = run(args, input);
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("needs the astar solver"));
+    }
+}
+
+#[test]
+fn hex_shape_and_svg_format() {
+    let input = "XXXXX\nXSXXX\nXX  E\nXXXXX\n";
+
+    let output = run(&["--shape", "hex", "-a", "bfs"], input);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "⬜⬜⬜⬜⬜\n ⬜❌⬜⬜⬜\n⬜⬜👣👣🏁\n ⬜⬜⬜⬜⬜\n"
+    );
+
+    let output = run(&["--shape", "hex", "--format", "svg"], input);
+    assert_eq!(output.status.code(), Some(0));
+    let svg = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(svg.matches("<polygon ").count(), 20);
+    assert_eq!(svg.matches("<polyline ").count(), 1);
+
+    assert_eq!(run(&["--shape", "circle"], input).status.code(), Some(2));
+}
+
+#[test]
+fn torus_topology_option() {
+    let input = "XXXXXX\n S X E\nXXXXXX\n";
+    assert_eq!(run(&["-a", "bfs"], input).status.code(), Some(1));
+
+    let output = run(&["-a", "bfs", "--topology", "torus"], input);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "⬜⬜⬜⬜⬜⬜\n👣❌⬛⬜⬛🏁\n⬜⬜⬜⬜⬜⬜\n"
+    );
+
+    let output = run(&["--topology", "torus"], "XXXXX\n S   \nXXXXX\n");
+    assert_eq!(output.status.code(), Some(2));
+    // hex rows only line up across the edges with an even height
+    let input = "XXXXXX\n S X E\nXXXXXX\n";
+    let output = run(
+        &["-a", "bfs", "--shape", "hex", "--topology", "torus"],
+        input,
+    );
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn keys_solver_reports_the_keys() {
+    let output = run(&["-a", "keys"], "XXXXXXXX\nXaBS AEX\nXXX XXXX\nXXXbXXXX\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "Keys: b, a\n");
+
+    let output = run(&["-a", "keys"], "XXXXX\nXS EX\nXXXXX\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "Keys: none\n");
+
+    let output = run(&["-a", "bfs"], "XXXXXXXX\nXaBS AEX\nXXX XXXX\nXXXbXXXX\n");
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn sliding_movement_option() {
+    let input = "XXXXXX\nXS   X\nX  X X\nX   EX\nXXXXXX\n";
+    let output = run(&["-a", "bfs", "-m", "sliding"], input);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "⬜⬜⬜⬜⬜⬜\n⬜❌⬛⬛👣⬜\n⬜⬛⬛⬜⬛⬜\n⬜⬛⬛⬛🏁⬜\n⬜⬜⬜⬜⬜⬜\n"
+    );
+}
+
+#[test]
+fn svg_size_options() {
+    let input = "XXXXX\nXS EX\nXXXXX\n";
+    let output = run(&["-f", "svg", "--cell-size", "10", "--margin", "5"], input);
+    assert_eq!(output.status.code(), Some(0));
+    let svg = String::from_utf8(output.stdout).unwrap();
+    assert!(svg.contains("width=\"60.00\" height=\"40.00\""));
+    assert!(svg.contains("<polyline points=\"20.00,20.00 30.00,20.00 40.00,20.00\""));
+
+    assert_eq!(run(&["--cell-size", "big"], input).status.code(), Some(2));
+    assert_eq!(run(&["--margin", "-1"], input).status.code(), Some(2));
+}
+
+#[test]
+fn png_format() {
+    let input = "XXXXX\nXS EX\nXXXXX\n";
+    let output = run(&["-f", "png", "--cell-size", "2"], input);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.starts_with(b"\x89PNG\r\n\x1a\n"));
+    // width and height of the header
+    assert_eq!(output.stdout[16..24], [0, 0, 0, 10, 0, 0, 0, 6]);
+
+    let output = run(&["-f", "png", "--cell-size", "2.5"], input);
+    assert_eq!(output.status.code(), Some(2));
+    for cell_size in ["5000000000", "1e19"] {
+        let output = run(&["-f", "png", "--cell-size", cell_size], input);
+        assert_eq!(output.status.code(), Some(2));
+    }
+}
+
+#[test]
+fn image_input() {
+    let input = "XXXXX\nXS EX\nXXXXX\n";
+    let png = run(&["-f", "png", "--cell-size", "3"], input).stdout;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_maze"))
+        .args(["--input", "image", "--pixels-per-cell", "3"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(&png).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "⬜⬜⬜⬜⬜\n⬜❌👣🏁⬜\n⬜⬜⬜⬜⬜\n"
+    );
+
+    let output = run(&["--input", "image"], input);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run(&["--input", "image"], "P6 6148914691236517205 1 255\n");
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(run(&["--threshold", "256"], input).status.code(), Some(2));
+}
+
+#[test]
+fn text_styles() {
+    let input = "XXXXX\nXS EX\nXXXXX\n";
+    let output = run(&["-a", "bfs", "--style", "ascii"], input);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "XXXXX\nXS.EX\nXXXXX\n"
+    );
+    // the ascii output reads back
+    let output = run(&["-a", "bfs", "--style", "ascii"], "XXXXX\nXS.EX\nXXXXX\n");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "XXXXX\nXS.EX\nXXXXX\n"
+    );
+    // a start on a road keeps the road and is listed after the rows
+    let output = run(&["-s", "2,1", "--style", "ascii"], "XXXXX\nX =EX\nXXXXX\n");
+    assert_eq!(output.status.code(), Some(0));
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(text, "XXXXX\nX =EX\nXXXXX\n@start 2,1\n@path 2,1\n");
+    let output = run(&["--style", "ascii"], &text);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), text);
+
+    let output = run(&["--style", "box"], input);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "┌───┐\n│S.E│\n└───┘\n"
+    );
+
+    assert_eq!(run(&["--style", "fancy"], input).status.code(), Some(2));
+}
+
+#[test]
+fn failed_output_is_not_invalid_input() {
+    // writing to a full device fails, where there is one
+    let Ok(full) = File::options().write(true).open("/dev/full") else {
+        return;
+    };
+    let mut child = Command::new(env!("CARGO_BIN_EXE_maze"))
+        .stdin(Stdio::piped())
+        .stdout(full)
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"XXXXX\nXS EX\nXXXXX\n")
+        .unwrap();
+    assert_eq!(child.wait().unwrap().code(), Some(3));
+}
@@ -0,0 +1,34 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use maze::{
+    AldousBroder, BinaryTree, CellSelection, Eller, Generator, GrowingTree, HuntAndKill, Kruskal,
+    Maze, Prim, RecursiveBacktracker, RecursiveDivision, Sidewinder, Wilson,
+};
+
+/// Every generator with the given seed.
+pub fn generators(seed: u64) -> Vec<Box<dyn Generator>> {
+    vec![
+        Box::new(RecursiveBacktracker::new(seed)),
+        Box::new(Prim::new(seed)),
+        Box::new(Kruskal::new(seed)),
+        Box::new(Wilson::new(seed)),
+        Box::new(AldousBroder::new(seed)),
+        Box::new(Eller::new(seed)),
+        Box::new(Sidewinder::new(seed)),
+        Box::new(BinaryTree::new(seed)),
+        Box::new(HuntAndKill::new(seed)),
+        Box::new(GrowingTree::new(seed)),
+        Box::new(GrowingTree::new(seed).with_selection(CellSelection::Oldest)),
+        Box::new(GrowingTree::new(seed).with_selection(CellSelection::Random)),
+        Box::new(
+            GrowingTree::new(seed).with_selection(CellSelection::Mixed { newest_percent: 50 }),
+        ),
+        Box::new(RecursiveDivision::new(seed)),
+    ]
+}
+
+/// Straight corridor from the start to the exit.
+pub fn corridor() -> Maze {
+    "XXXXX\nXS EX\nXXXXX".parse().unwrap()
+}
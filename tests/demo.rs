@@ -0,0 +1,171 @@
+use maze::{Heuristic, Maze, Solvable, SolveAlgorithm};
+
+fn bool_array_maze() -> Maze {
+    Maze::new_from_bool_array(
+        vec![
+            vec![true, false, true],
+            vec![false, true, false],
+            vec![true, true, false],
+        ],
+        1,
+        1,
+    )
+    .expect("Error while creating maze!")
+}
+
+fn str_array_maze() -> Maze {
+    Maze::new_from_str_array(vec![" X ", "X X", "  X"], 1, 1)
+        .expect("Error while creating maze!")
+        .set_start(1, 2)
+        .expect("Error on update starting position")
+}
+
+fn ten_by_ten_maze() -> Maze {
+    Maze::new_from_str(
+        &("XXX  XX   \n".to_owned()
+            + "X     X  X\n"
+            + "X XX  XX X\n"
+            + "X   XXX   \n"
+            + "X    X  XX\n"
+            + "XX  XX  X \n"
+            + "X  X  X X \n"
+            + "X   X   XX\n"
+            + "X XXXX XXX\n"
+            + "XX  XX  XX"),
+        4,
+        4,
+    )
+    .expect("Error while creating maze!")
+}
+
+fn enclosed_maze() -> Maze {
+    Maze::new_from_str(
+        &("XXXXXXXXX\n".to_owned()
+            + "XXXXXXXXX\n"
+            + "XXXXXXXXX\n"
+            + "XXX   XXX\n"
+            + "XXX   XXX\n"
+            + "XXX   XXX\n"
+            + "XXX   XXX\n"
+            + "XXXXXXXXX\n"
+            + "XXXXXXXXX\n"
+            + "XXXXXXXXX"),
+        4,
+        4,
+    )
+    .expect("Error while creating maze!")
+}
+
+#[test]
+fn bool_array_maze_is_solved() {
+    let mut maze = bool_array_maze();
+    assert_eq!((maze.width(), maze.height()), (3, 3));
+    assert_eq!(maze.to_string(), "⬛⬜⬛\n⬜❌⬜\n⬛⬛⬜\n");
+
+    assert_eq!(maze.solve(), Ok(true));
+    assert_eq!(maze.to_string(), "⬛⬜⬛\n⬜❌⬜\n⬛👣⬜\n");
+}
+
+#[test]
+fn str_array_maze_starts_on_exit() {
+    let maze = str_array_maze();
+    assert_eq!((maze.start_x(), maze.start_y()), (1, 2));
+    assert_eq!(maze.find_path(), Ok(Some(vec![(1, 2)])));
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(vec![(1, 2)]))
+    );
+}
+
+#[test]
+fn ten_by_ten_maze_depth_first() {
+    let mut maze = ten_by_ten_maze();
+    assert_eq!(maze.solve_with(SolveAlgorithm::DepthFirst), Ok(true));
+    assert_eq!(
+        maze.to_string(),
+        "⬜⬜⬜⬛👣⬜⬜⬛⬛⬛\n\
+         ⬜👣👣👣👣⬛⬜⬛⬛⬜\n\
+         ⬜👣⬜⬜⬛⬛⬜⬜⬛⬜\n\
+         ⬜👣⬛⬛⬜⬜⬜⬛⬛⬛\n\
+         ⬜👣👣👣❌⬜⬛⬛⬜⬜\n\
+         ⬜⬜⬛⬛⬜⬜⬛⬛⬜⬛\n\
+         ⬜⬛⬛⬜⬛⬛⬜⬛⬜⬛\n\
+         ⬜⬛⬛⬛⬜⬛⬛⬛⬜⬜\n\
+         ⬜⬛⬜⬜⬜⬜⬛⬜⬜⬜\n\
+         ⬜⬜⬛⬛⬜⬜⬛⬛⬜⬜\n"
+    );
+}
+
+#[test]
+fn ten_by_ten_maze_breadth_first_is_shortest() {
+    let maze = ten_by_ten_maze();
+    let depth_first = maze.find_path().unwrap().unwrap();
+    let shortest = maze
+        .find_path_with(SolveAlgorithm::BreadthFirst)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(depth_first.len() - 1, 10);
+    assert_eq!(shortest.len() - 1, 9);
+    assert_eq!(shortest.first(), Some(&(4, 4)));
+    assert_eq!(shortest.last(), Some(&(3, 0)));
+
+    // finding a path leaves the maze untouched
+    assert_eq!(maze.to_string(), ten_by_ten_maze().to_string());
+}
+
+#[test]
+fn ten_by_ten_maze_astar_heuristics() {
+    let maze = ten_by_ten_maze();
+
+    for heuristic in [
+        Heuristic::Manhattan,
+        Heuristic::Chebyshev,
+        Heuristic::Euclidean,
+    ] {
+        let (path, stats) = maze.find_path_astar(&[(1, 8)], heuristic).unwrap();
+        assert_eq!(path.map(|path| path.len() - 1), Some(7));
+        assert_eq!(stats.path_cost, Some(7));
+        assert_eq!(stats.nodes_expanded, 9);
+    }
+
+    let (_, stats) = maze.find_path_astar(&[(1, 8)], Heuristic::Zero).unwrap();
+    assert_eq!(stats.path_cost, Some(7));
+    assert_eq!(stats.nodes_expanded, 18);
+}
+
+#[test]
+fn enclosed_maze_has_no_solution() {
+    let mut maze = enclosed_maze();
+    assert_eq!((maze.width(), maze.height()), (9, 10));
+    assert_eq!(maze.find_path_with(SolveAlgorithm::BreadthFirst), Ok(None));
+    assert_eq!(maze.solve(), Ok(false));
+}
+
+#[test]
+fn huge_open_maze_is_solved_on_a_small_stack() {
+    // a single exit in the far corner, so depth-first search snakes through every row
+    let size = 2000;
+    let map = (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| x > 0 && y > 0 && x < size - 1 && y < size - 1)
+                .collect()
+        })
+        .collect();
+    let mut maze = Maze::new_from_bool_array(map, 1, 1)
+        .and_then(|maze| maze.set_exits(vec![(size - 2, size - 2)]))
+        .unwrap();
+
+    // recursing once per cell would overflow this stack long before the exit
+    let solved = std::thread::Builder::new()
+        .stack_size(256 * 1024)
+        .spawn(move || {
+            let path = maze.find_path().unwrap().unwrap();
+            (path.len(), maze.solve())
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    assert_eq!(solved, (3_990_007, Ok(true)));
+}
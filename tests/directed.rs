@@ -0,0 +1,113 @@
+use maze::{Direction, FloorType, Heuristic, Maze, MazeCell, MazeError, Solvable, SolveAlgorithm};
+
+#[test]
+fn arrows_are_parsed_and_displayed() {
+    let maze: Maze = "XXXXXXXXXX\nX<>^v4682S\nXXXXXXXXXX".parse().unwrap();
+    assert_eq!(
+        maze.to_string(),
+        "⬜⬜⬜⬜⬜⬜⬜⬜⬜⬜\n⬜👈👉👆👇⏪⏩⏫⏬❌\n⬜⬜⬜⬜⬜⬜⬜⬜⬜⬜\n"
+    );
+    assert_eq!(
+        maze.with_path(&[(9, 1), (8, 1)]).unwrap().to_string(),
+        maze.to_string()
+    );
+
+    // 'v' is an arrow, so 'V' can't be a door
+    assert_eq!(
+        "XXXX\nXSVE\nXXXX".parse::<Maze>().unwrap_err(),
+        MazeError::UnknownCharacter {
+            character: 'V',
+            line: 2,
+            column: 3
+        }
+    );
+}
+
+#[test]
+fn one_way_cells_are_left_in_their_direction() {
+    let maze: Maze = "XXXXXXX\nXE < SX\nXXXXXXX".parse().unwrap();
+    let path = vec![(5, 1), (4, 1), (3, 1), (2, 1), (1, 1)];
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(path))
+    );
+    assert_eq!(maze.trap_cells(), Ok(Vec::new()));
+
+    // entering against the arrow is possible, but the only way out is back
+    let maze: Maze = "XXXXXXX\nXE > SX\nXXXXXXX".parse().unwrap();
+    for algorithm in [
+        SolveAlgorithm::DepthFirst,
+        SolveAlgorithm::BreadthFirst,
+        SolveAlgorithm::Dijkstra,
+    ] {
+        assert_eq!(maze.find_path_with(algorithm), Ok(None), "{:?}", algorithm);
+    }
+    assert_eq!(maze.reachable_cells(), Ok(vec![(3, 1), (4, 1), (5, 1)]));
+    assert_eq!(maze.trap_cells(), Ok(vec![(3, 1), (4, 1), (5, 1)]));
+
+    // the route back from the exit differs from the way there
+    let maze: Maze = ["XXXXXX", "XS>  X", "X X  X", "X <  X", "XXXEXX"]
+        .join("\n")
+        .parse()
+        .unwrap();
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(vec![(1, 1), (2, 1), (3, 1), (3, 2), (3, 3), (3, 4)]))
+    );
+    let back = maze
+        .set_start(3, 4)
+        .unwrap()
+        .set_exits(vec![(1, 1)])
+        .unwrap();
+    assert_eq!(
+        back.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(vec![(3, 4), (3, 3), (2, 3), (1, 3), (1, 2), (1, 1)]))
+    );
+}
+
+#[test]
+fn conveyors_move_the_walker_for_free() {
+    let maze: Maze = "XXXXXXXX\nXS666 EX\nXXXXXXXX".parse().unwrap();
+    let path = vec![(1, 1), (2, 1), (3, 1), (4, 1), (5, 1), (6, 1)];
+    assert_eq!(maze.find_cheapest_path(), Ok(Some((path.clone(), 2))));
+    assert_eq!(maze.path_cost(&path), Ok(2));
+    for heuristic in [Heuristic::Manhattan, Heuristic::Euclidean] {
+        let (found, stats) = maze.find_path_astar(&[(6, 1)], heuristic).unwrap();
+        assert_eq!(found, Some(path.clone()));
+        assert_eq!(stats.path_cost, Some(2));
+    }
+
+    // against the belt there is no way through
+    let maze: Maze = "XXXXXXXX\nXE666 SX\nXXXXXXXX".parse().unwrap();
+    assert_eq!(maze.find_path(), Ok(None));
+}
+
+#[test]
+fn cells_that_can_be_entered_but_never_left() {
+    // the conveyor drops into a dead end below the corridor
+    let maze: Maze = ["XXXXXXX", "XS    E", "XXX2XXX", "XXX XXX", "XXXXXXX"]
+        .join("\n")
+        .parse()
+        .unwrap();
+    assert!(maze.find_path().unwrap().is_some());
+    assert_eq!(maze.trap_cells(), Ok(vec![(3, 2), (3, 3)]));
+
+    let conveyor = MazeCell::Floor(FloorType::Conveyor(Direction::Up));
+    let maze = Maze::new(
+        vec![
+            vec![MazeCell::Wall, MazeCell::Wall, MazeCell::Wall],
+            vec![MazeCell::Wall, conveyor, MazeCell::Wall],
+            vec![
+                MazeCell::Wall,
+                MazeCell::Floor(FloorType::Floor),
+                MazeCell::Wall,
+            ],
+        ],
+        1,
+        1,
+    )
+    .unwrap();
+    // starting on a conveyor into a wall
+    assert_eq!(maze.reachable_cells(), Ok(vec![(1, 1)]));
+    assert_eq!(maze.trap_cells(), Ok(vec![(1, 1)]));
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::generators;
+use maze::{
+    Generator, Maze, MazeError, RecursiveBacktracker, RecursiveDivision, Solvable, SolveAlgorithm,
+};
+
+/// Number of floor cells in the display output of `maze`.
+fn floor_count(maze: &Maze) -> usize {
+    maze.to_string()
+        .chars()
+        .filter(|&c| c == '⬛' || c == '❌')
+        .count()
+}
+
+#[test]
+fn generators_are_deterministic() {
+    for (first, second) in generators(42).iter().zip(generators(42)) {
+        assert_eq!(
+            first.generate(12, 8).unwrap().to_string(),
+            second.generate(12, 8).unwrap().to_string()
+        );
+    }
+
+    let first = RecursiveBacktracker::new(42).generate(12, 8).unwrap();
+    let other = RecursiveBacktracker::new(43).generate(12, 8).unwrap();
+    assert_ne!(first.to_string(), other.to_string());
+}
+
+#[test]
+fn generators_create_solvable_perfect_mazes() {
+    for seed in 0..10 {
+        for (index, generator) in generators(seed).into_iter().enumerate() {
+            for (width, height) in [(10, 7), (1, 5), (6, 1), (1, 1)] {
+                let maze = generator.generate(width, height).unwrap();
+                assert_eq!(
+                    (maze.width(), maze.height()),
+                    (2 * width + 1, 2 * height + 1)
+                );
+                assert_eq!((maze.start_x(), maze.start_y()), (1, 1));
+
+                // every cell, one passage less than cells (a spanning tree) and the exit
+                assert_eq!(
+                    floor_count(&maze),
+                    2 * width * height,
+                    "generator {}, seed {}",
+                    index,
+                    seed
+                );
+
+                let path = maze.find_path_with(SolveAlgorithm::BreadthFirst).unwrap();
+                assert_eq!(
+                    path.and_then(|path| path.last().copied()).map(|(x, _)| x),
+                    Some(2 * width)
+                );
+
+                // all cells are connected to the exit
+                for y in 0..height {
+                    for x in 0..width {
+                        let from_cell = maze.clone().set_start(2 * x + 1, 2 * y + 1).unwrap();
+                        assert!(from_cell.find_path().unwrap().is_some());
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn generators_handle_large_mazes() {
+    let maze = RecursiveBacktracker::new(7).generate(300, 300).unwrap();
+    assert!(maze.find_path().unwrap().is_some());
+    let maze = RecursiveDivision::new(7).generate(300, 300).unwrap();
+    assert!(maze.find_path().unwrap().is_some());
+}
+
+#[test]
+fn generator_rejects_empty_size() {
+    for generator in generators(0) {
+        assert_eq!(
+            generator.generate(0, 5).unwrap_err(),
+            MazeError::TooSmall {
+                width: 1,
+                height: 11
+            }
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use maze::{Heuristic, HexBacktracker, Maze, MazeError, Shape, Solvable, SolveAlgorithm};
+
+/// Corridor along odd-r offset rows, only passable with hexagon neighbours.
+fn zigzag() -> Maze {
+    ["XXXXX", "XSXXX", "XX XX", "XX  E", "XXXXX"]
+        .join("\n")
+        .parse::<Maze>()
+        .unwrap()
+        .with_shape(Shape::Hex)
+}
+
+#[test]
+fn hex_neighbours_follow_the_odd_rows() {
+    let maze = zigzag();
+    assert_eq!(maze.shape(), Shape::Hex);
+
+    for algorithm in [
+        SolveAlgorithm::DepthFirst,
+        SolveAlgorithm::BreadthFirst,
+        SolveAlgorithm::Dijkstra,
+    ] {
+        assert_eq!(
+            maze.find_path_with(algorithm),
+            Ok(Some(vec![(1, 1), (2, 2), (2, 3), (3, 3), (4, 3)])),
+            "{:?}",
+            algorithm
+        );
+    }
+    let (path, stats) = maze
+        .find_path_astar(&[(4, 3)], Heuristic::Chebyshev)
+        .unwrap();
+    assert_eq!(path.map(|path| path.len()), Some(5));
+    assert_eq!(stats.path_cost, Some(4));
+
+    // the same cells only touch at their corners as squares
+    let square = maze.with_shape(Shape::Square);
+    assert_eq!(square.find_path(), Ok(None));
+}
+
+#[test]
+fn hex_rows_are_shifted_in_the_text_output() {
+    assert_eq!(
+        zigzag().to_string(),
+        "⬜⬜⬜⬜⬜\n ⬜❌⬜⬜⬜\n⬜⬜⬛⬜⬜\n ⬜⬜⬛⬛🏁\n⬜⬜⬜⬜⬜\n"
+    );
+}
+
+#[test]
+fn svg_draws_one_shape_per_cell() {
+    let maze = zigzag();
+    let svg = maze.to_svg();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches("<polygon ").count(), 25);
+
+    let svg = maze.with_shape(Shape::Square).to_svg();
+    assert_eq!(svg.matches("<rect ").count(), 25);
+    assert!(svg.contains("width=\"100.00\" height=\"100.00\""));
+}
+
+#[test]
+fn hex_backtracker_creates_perfect_mazes() {
+    // every small size, where the border is most likely to need a tunnel to the corridors
+    let sizes = (3..=12).flat_map(|width| (3..=9).map(move |height| (width, height)));
+    for seed in 0..20 {
+        for (width, height) in sizes.clone().chain([(25, 17)]) {
+            let maze = HexBacktracker::new(seed).generate(width, height).unwrap();
+            assert_eq!(maze.shape(), Shape::Hex);
+            assert_eq!((maze.width(), maze.height()), (width, height));
+
+            // every floor cell is reachable and the corridors form a tree
+            let floor = maze.reachable_cells().unwrap();
+            let text = maze.to_string();
+            assert_eq!(
+                floor.len(),
+                text.chars().filter(|&c| c == '⬛' || c == '❌').count()
+            );
+            let links: usize = floor
+                .iter()
+                .map(|&cell| {
+                    floor
+                        .iter()
+                        .filter(|&&other| are_hex_neighbours(cell, other))
+                        .count()
+                })
+                .sum();
+            assert_eq!(links / 2, floor.len() - 1, "seed {}", seed);
+
+            let path = maze.find_path().unwrap().expect("maze has an exit");
+            assert_eq!(path.last().map(|&(x, _)| x), Some(width - 1));
+        }
+    }
+
+    assert_eq!(
+        HexBacktracker::new(0).generate(9, 6).unwrap().to_string(),
+        HexBacktracker::new(0).generate(9, 6).unwrap().to_string()
+    );
+    assert_eq!(
+        HexBacktracker::new(0).generate(2, 5).unwrap_err(),
+        MazeError::TooSmall {
+            width: 2,
+            height: 5
+        }
+    );
+}
+
+fn are_hex_neighbours(a: (usize, usize), b: (usize, usize)) -> bool {
+    let shift = |(x, y): (usize, usize)| 2 * x + y % 2;
+    (a.1 == b.1 && shift(a).abs_diff(shift(b)) == 2)
+        || (a.1.abs_diff(b.1) == 1 && shift(a).abs_diff(shift(b)) == 1)
+}
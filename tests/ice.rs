@@ -0,0 +1,123 @@
+use maze::{
+    FloorType, Heuristic, IcePuzzle, Maze, MazeError, Movement, Solvable, SolveAlgorithm, Topology,
+};
+
+/// Open room where sliding needs only two moves to reach the exit.
+fn room() -> Maze {
+    ["XXXXXX", "XS   X", "X  X X", "X   EX", "XXXXXX"]
+        .join("\n")
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn sliding_moves_until_blocked() {
+    let maze = room().with_movement(Movement::Sliding);
+    assert_eq!(maze.movement(), Movement::Sliding);
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(vec![(1, 1), (4, 1), (4, 3)]))
+    );
+    assert_eq!(
+        maze.find_cheapest_path(),
+        Ok(Some((vec![(1, 1), (4, 1), (4, 3)], 2)))
+    );
+    let (path, stats) = maze
+        .find_path_astar(&[(4, 3)], Heuristic::Manhattan)
+        .unwrap();
+    assert_eq!(path.map(|path| path.len()), Some(3));
+    assert_eq!(stats.path_cost, Some(2));
+    assert_eq!(
+        maze.reachable_cells(),
+        Ok(vec![(1, 1), (4, 1), (1, 3), (4, 3)])
+    );
+
+    // walking takes every step
+    let path = room().find_path_with(SolveAlgorithm::BreadthFirst).unwrap();
+    assert_eq!(path.map(|path| path.len()), Some(6));
+}
+
+#[test]
+fn sliding_past_the_exit_does_not_stop() {
+    let maze = "XXXXXX\nXSE  X\nXXXXXX"
+        .parse::<Maze>()
+        .unwrap()
+        .with_movement(Movement::Sliding);
+    assert_eq!(maze.find_path_with(SolveAlgorithm::BreadthFirst), Ok(None));
+    assert_eq!(maze.trap_cells(), Ok(vec![(1, 1), (4, 1)]));
+
+    // around a torus the slide never stops
+    let maze = Maze::new_from_str("XXXX\n    \nXXXX", 1, 1)
+        .unwrap()
+        .with_topology(Topology::Torus)
+        .with_movement(Movement::Sliding)
+        .set_exits(vec![(3, 1)])
+        .unwrap();
+    assert_eq!(maze.find_path(), Ok(None));
+    assert_eq!(maze.reachable_cells(), Ok(vec![(1, 1)]));
+}
+
+#[test]
+fn only_ice_slides() {
+    let maze: Maze = "XXXXXXX\nXS** EX\nXXXXXXX".parse().unwrap();
+    assert_eq!(
+        maze.to_string(),
+        "⬜⬜⬜⬜⬜⬜⬜\n⬜❌🧊🧊⬛🏁⬜\n⬜⬜⬜⬜⬜⬜⬜\n"
+    );
+    assert_eq!(FloorType::Ice.cost(), FloorType::Floor.cost());
+    assert_eq!(
+        maze.find_cheapest_path(),
+        Ok(Some((vec![(1, 1), (4, 1), (5, 1)], 2)))
+    );
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(vec![(1, 1), (4, 1), (5, 1)]))
+    );
+
+    // a rock stops the slide on the ice
+    let maze: Maze = "XXXXXX\nXS**XX\nXXX EX\nXXXXXX".parse().unwrap();
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(vec![(1, 1), (3, 1), (3, 2), (4, 2)]))
+    );
+}
+
+#[test]
+fn generated_puzzles_are_solvable() {
+    for seed in 0..10 {
+        let generator = IcePuzzle::new(seed);
+        let maze = generator.generate(12, 9).unwrap();
+        assert_eq!((maze.width(), maze.height()), (12, 9));
+        assert_eq!(maze.movement(), Movement::Sliding);
+        assert_eq!(
+            maze.to_string(),
+            generator.generate(12, 9).unwrap().to_string()
+        );
+
+        let exit = maze.exits()[0];
+        let path = maze
+            .find_path_with(SolveAlgorithm::BreadthFirst)
+            .unwrap()
+            .unwrap();
+        assert_eq!(path.last(), Some(&exit));
+        // more than a single slide away
+        assert!(path.len() > 2, "seed {}: {:?}", seed, path);
+    }
+
+    let rocks = IcePuzzle::new(3).with_rocks(40).generate(12, 9).unwrap();
+    let empty = IcePuzzle::new(3).with_rocks(0).generate(12, 9).unwrap();
+    let walls = |maze: &Maze| maze.to_string().chars().filter(|&c| c == '⬜').count();
+    assert!(walls(&rocks) > walls(&empty));
+    assert_eq!(walls(&empty), 2 * 12 + 2 * 7);
+
+    // a single cell inside the border is solved without moving
+    let tiny = IcePuzzle::new(1).generate(3, 3).unwrap();
+    assert_eq!(tiny.find_path(), Ok(Some(vec![(1, 1)])));
+    assert_eq!(
+        IcePuzzle::new(1).generate(2, 5).unwrap_err(),
+        MazeError::TooSmall {
+            width: 2,
+            height: 5
+        }
+    );
+}
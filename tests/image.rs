@@ -0,0 +1,218 @@
+use maze::{Generator, ImageImport, Maze, MazeError, RecursiveBacktracker, Solvable};
+
+/// Paletted PNG of 2 bits per pixel with dynamic Huffman codes, one pixel per cell.
+const PALETTE_PNG: [u8; 178] = [
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00, 0x13, 0x02, 0x03, 0x00, 0x00, 0x00, 0x15, 0x02, 0x3e,
+    0xd1, 0x00, 0x00, 0x00, 0x0c, 0x50, 0x4c, 0x54, 0x45, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0x00,
+    0xc8, 0x00, 0xdc, 0x00, 0x00, 0x95, 0x2d, 0x18, 0x45, 0x00, 0x00, 0x00, 0x61, 0x49, 0x44, 0x41,
+    0x54, 0x78, 0xda, 0x3d, 0x4e, 0xcb, 0x11, 0x43, 0x41, 0x08, 0xd2, 0x0e, 0xe4, 0xc0, 0x2d, 0xd7,
+    0xf4, 0xb3, 0x1c, 0xe8, 0xbf, 0x95, 0xe0, 0x4e, 0xde, 0x43, 0xc7, 0xdf, 0x20, 0x5a, 0xf5, 0xe0,
+    0x2b, 0x5b, 0xe6, 0xa9, 0x42, 0xbc, 0x13, 0x08, 0x9a, 0xc0, 0x29, 0x60, 0xba, 0x31, 0x53, 0x10,
+    0x25, 0x79, 0x2a, 0x16, 0x24, 0xc1, 0xf8, 0x4f, 0xd0, 0x8b, 0x29, 0xca, 0x08, 0xeb, 0xac, 0x26,
+    0xaa, 0xe7, 0x4e, 0x48, 0x31, 0x9c, 0x74, 0xe1, 0x5d, 0x9d, 0x2c, 0x22, 0x45, 0x0e, 0xf5, 0x16,
+    0xb9, 0x05, 0x41, 0xab, 0x33, 0xa1, 0xad, 0x0e, 0x69, 0xd3, 0x9f, 0xf7, 0xc1, 0x1f, 0xb1, 0xd8,
+    0x10, 0x2a, 0x25, 0xcf, 0x26, 0x27, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42,
+    0x60, 0x82,
+];
+
+/// Maze drawn in [`PALETTE_PNG`].
+const PALETTE_MAZE: &str = "\
+XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX
+XS    X           X     X     X
+XXXXX X XXXXXXXXX X XXX X X XXX
+X   X X X       X   X X X X   X
+X X X X X XXXXX XXX X X X XXX X
+X X   X X     X   X   X     X X
+X XXXXXXX XXX XXX XXX XXXXXXX X
+X X     X X   X   X   X     X X
+X XXX X XXX XXX XXX XXX XXX X X
+X     X     X X X     X   X   X
+XXXXXXXXXXXXX X XXXXXXX X XXX X
+X     X     X   X     X X   X X
+X XXXXX X XXX XXX XXX X XXX X X
+X X   X X   X X     X X X X X X
+X X X X XXX X X XXX X X X X X X
+X   X X X   X X   X X X   X X X
+X XXX X X XXX XXXXX X XXXXX X X
+X   X   X           X       X E
+XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+
+/// `png` with the width and height in its header replaced and the checksum updated.
+fn with_size(png: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut png = png.to_vec();
+    png[16..20].copy_from_slice(&width.to_be_bytes());
+    png[20..24].copy_from_slice(&height.to_be_bytes());
+    let mut crc = u32::MAX;
+    for &byte in &png[12..29] {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    png[29..33].copy_from_slice(&(!crc).to_be_bytes());
+    png
+}
+
+#[test]
+fn png_export_reads_back() {
+    let maze = RecursiveBacktracker::new(5).generate(8, 6).unwrap();
+    let path = maze.find_path().unwrap().unwrap();
+    let png = maze.to_png(4, &path).unwrap();
+
+    let read = Maze::from_image(&png, &ImageImport::default().with_cell_size(4)).unwrap();
+    // the solution is light floor
+    assert_eq!(read.to_string(), maze.to_string());
+    assert_eq!((read.start_x(), read.start_y()), (1, 1));
+    assert!(read.exits().is_empty());
+
+    let maze = maze.set_exits(vec![(15, 11)]).unwrap();
+    let read = Maze::from_image(&maze.to_png(1, &[]).unwrap(), &ImageImport::default()).unwrap();
+    assert_eq!(read.exits(), [(15, 11)]);
+}
+
+#[test]
+fn paletted_png_is_read() {
+    let read = Maze::from_image(&PALETTE_PNG, &ImageImport::default()).unwrap();
+    let maze: Maze = PALETTE_MAZE.parse().unwrap();
+    assert_eq!(read.to_string(), maze.to_string());
+    assert_eq!(read.exits(), maze.exits());
+}
+
+#[test]
+fn netpbm_images_are_read() {
+    let text = "XXXXX\nXS EX\nXXXXX";
+    let maze: Maze = text.parse().unwrap();
+
+    let colour = |c: char| match c {
+        'X' => [0, 0, 0],
+        'S' => [0, 255, 0],
+        'E' => [255, 0, 0],
+        _ => [255, 255, 255],
+    };
+    let mut ppm = b"P6\n# a comment\n5 3\n255\n".to_vec();
+    ppm.extend(text.lines().flat_map(str::chars).flat_map(colour));
+    let read = Maze::from_image(&ppm, &ImageImport::default()).unwrap();
+    assert_eq!(read.to_string(), maze.to_string());
+
+    let samples: Vec<String> = text
+        .lines()
+        .flat_map(str::chars)
+        .flat_map(colour)
+        .map(|value| (value as usize * 257).to_string())
+        .collect();
+    let ppm = format!("P3 5 3 65535\n{}\n", samples.join(" "));
+    let read = Maze::from_image(ppm.as_bytes(), &ImageImport::default()).unwrap();
+    assert_eq!(read.to_string(), maze.to_string());
+
+    // grey images need an explicit start, 2 x 2 pixels per cell
+    let pgm = "P2 10 6 9\n\
+               0 0 0 0 0 0 0 0 0 0\n0 0 0 0 0 0 0 0 0 0\n\
+               0 0 9 9 6 6 9 9 0 0\n0 0 9 9 6 6 9 9 0 0\n\
+               0 0 0 0 0 0 0 0 0 0\n0 0 0 0 0 0 0 0 0 0";
+    let import = ImageImport::default().with_cell_size(2);
+    assert_eq!(
+        Maze::from_image(pgm.as_bytes(), &import).unwrap_err(),
+        MazeError::MissingStart
+    );
+    let read = Maze::from_image(pgm.as_bytes(), &import.clone().with_start(1, 1)).unwrap();
+    assert_eq!(read.to_string(), "⬜⬜⬜⬜⬜\n⬜❌⬛⬛⬜\n⬜⬜⬜⬜⬜\n");
+
+    // the middle cell is 6 / 9 bright, below a threshold of 200
+    let read =
+        Maze::from_image(pgm.as_bytes(), &import.with_start(1, 1).with_threshold(200)).unwrap();
+    assert_eq!(read.to_string(), "⬜⬜⬜⬜⬜\n⬜❌⬜⬛⬜\n⬜⬜⬜⬜⬜\n");
+}
+
+#[test]
+fn invalid_images_are_rejected() {
+    let import = ImageImport::default();
+    assert_eq!(
+        Maze::from_image(b"XXXXX\nXS EX\nXXXXX", &import).unwrap_err(),
+        MazeError::InvalidImage {
+            reason: "unknown image format"
+        }
+    );
+    assert_eq!(
+        Maze::from_image(b"P5 5 3 255\n\0\0\0", &import).unwrap_err(),
+        MazeError::InvalidImage {
+            reason: "image ends early"
+        }
+    );
+    assert_eq!(
+        Maze::from_image(&PALETTE_PNG, &import.clone().with_cell_size(0)).unwrap_err(),
+        MazeError::InvalidCellSize { size: 0 }
+    );
+    assert_eq!(
+        Maze::from_image(
+            &PALETTE_PNG,
+            &import.clone().with_cell_size(7).with_start(1, 1)
+        )
+        .unwrap_err(),
+        MazeError::TooSmall {
+            width: 4,
+            height: 2
+        }
+    );
+
+    let mut damaged = PALETTE_PNG;
+    damaged[60] ^= 1;
+    assert_eq!(
+        Maze::from_image(&damaged, &import).unwrap_err(),
+        MazeError::InvalidImage {
+            reason: "wrong checksum of PNG chunk"
+        }
+    );
+    assert!(Maze::from_image(&PALETTE_PNG[..100], &import).is_err());
+
+    let two_starts = b"P6 3 3 255\n\
+        \0\xff\0\0\xff\0\0\xff\0\xff\xff\xff\xff\xff\xff\xff\xff\xff\0\0\0\0\0\0\0\0\0";
+    assert_eq!(
+        Maze::from_image(two_starts, &import).unwrap_err(),
+        MazeError::MultipleStarts { x: 1, y: 0 }
+    );
+}
+
+#[test]
+fn malformed_headers_are_rejected() {
+    let import = ImageImport::default();
+    let invalid = |reason| MazeError::InvalidImage { reason };
+
+    let maze: Maze = "XXXXX\nXS EX\nXXXXX".parse().unwrap();
+    let png = maze.to_png(1, &[]).unwrap();
+    assert!(Maze::from_image(&with_size(&png, 5, 3), &import).is_ok());
+    let cases = [
+        (0, 3, "PNG image without pixels"),
+        (5, 0, "PNG image without pixels"),
+        (u32::MAX, u32::MAX, "PNG image too large"),
+        (5, 4, "PNG image ends early"),
+    ];
+    for (width, height, reason) in cases {
+        assert_eq!(
+            Maze::from_image(&with_size(&png, width, height), &import).unwrap_err(),
+            invalid(reason)
+        );
+    }
+
+    let cases: [(&[u8], _); 6] = [
+        (b"P6 0 3 255\n", "image without pixels"),
+        (b"P2 5 0 255\n", "image without pixels"),
+        (b"P6 6148914691236517205 1 255\n\0", "image ends early"),
+        (b"P5 99999999999 99999999999 255\n", "image too large"),
+        (
+            b"P3 99999999999999999999 1 255\n",
+            "number too large in image",
+        ),
+        (b"P2 2 2 255\n0 255 0", "image ends early"),
+    ];
+    for (image, reason) in cases {
+        assert_eq!(
+            Maze::from_image(image, &import).unwrap_err(),
+            invalid(reason)
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use maze::{FloorType, KeyPath, Maze, MazeCell, Solvable, SolveAlgorithm};
+
+/// The exit is behind door `A`, its key behind door `B` and that key down the side corridor.
+fn locked() -> Maze {
+    ["XXXXXXXXX", "XaB S AEX", "XXXX XXXX", "XXXXbXXXX"]
+        .join("\n")
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn letters_with_doors_are_keys() {
+    let maze = locked();
+    assert_eq!(
+        maze.to_string(),
+        "⬜⬜⬜⬜⬜⬜⬜⬜⬜\n\
+         ⬜🔑🚪⬛❌⬛🚪🏁⬜\n\
+         ⬜⬜⬜⬜⬛⬜⬜⬜⬜\n\
+         ⬜⬜⬜⬜🔑⬜⬜⬜⬜\n"
+    );
+    assert_eq!(maze.portal(1, 1), None);
+
+    // keys may be used more than once, doors without a key stay closed
+    let maze: Maze = "XXXXXXX\nXSaZa A\nXXXXXXX".parse().unwrap();
+    assert_eq!(maze.portal(2, 1), None);
+    assert_eq!(maze.find_path_with_keys(), Ok(None));
+}
+
+#[test]
+fn doors_are_closed_for_the_plain_solvers() {
+    let maze = locked();
+    for algorithm in [
+        SolveAlgorithm::DepthFirst,
+        SolveAlgorithm::BreadthFirst,
+        SolveAlgorithm::Dijkstra,
+    ] {
+        assert_eq!(maze.find_path_with(algorithm), Ok(None), "{:?}", algorithm);
+    }
+    assert_eq!(
+        maze.reachable_cells(),
+        Ok(vec![(3, 1), (4, 1), (5, 1), (4, 2), (4, 3)])
+    );
+}
+
+#[test]
+fn keys_are_collected_in_order() {
+    let maze = locked();
+    let found = maze.find_path_with_keys().unwrap().unwrap();
+    assert_eq!(
+        found,
+        KeyPath {
+            path: vec![
+                (4, 1),
+                (4, 2),
+                (4, 3),
+                (4, 2),
+                (4, 1),
+                (3, 1),
+                (2, 1),
+                (1, 1),
+                (2, 1),
+                (3, 1),
+                (4, 1),
+                (5, 1),
+                (6, 1),
+                (7, 1)
+            ],
+            keys: vec!['b', 'a'],
+            cost: 13,
+        }
+    );
+    assert_eq!(maze.path_cost(&found.path), Ok(found.cost));
+
+    // keys and doors stay visible on the marked route
+    assert_eq!(
+        maze.with_path(&found.path).unwrap().to_string(),
+        "⬜⬜⬜⬜⬜⬜⬜⬜⬜\n\
+         ⬜🔑🚪👣❌👣🚪🏁⬜\n\
+         ⬜⬜⬜⬜👣⬜⬜⬜⬜\n\
+         ⬜⬜⬜⬜🔑⬜⬜⬜⬜\n"
+    );
+}
+
+#[test]
+fn unreachable_keys() {
+    // the key is behind its own door
+    let maze: Maze = "XXXXXXX\nXS A aE\nXXXXXXX".parse().unwrap();
+    assert_eq!(maze.find_path_with_keys(), Ok(None));
+
+    // starting on a key holds it from the beginning
+    let start = MazeCell::Floor(FloorType::Key('q'));
+    let door = MazeCell::Floor(FloorType::Door('q'));
+    let maze = Maze::new(
+        vec![
+            vec![MazeCell::Wall; 3],
+            vec![MazeCell::Wall, start, door],
+            vec![MazeCell::Wall; 3],
+        ],
+        1,
+        1,
+    )
+    .unwrap();
+    assert_eq!(
+        maze.find_path_with_keys(),
+        Ok(Some(KeyPath {
+            path: vec![(1, 1), (2, 1)],
+            keys: vec!['q'],
+            cost: 1,
+        }))
+    );
+}
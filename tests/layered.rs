@@ -0,0 +1,182 @@
+use maze::{LayeredMaze, MazeCell, MazeError, SolveAlgorithm};
+
+/// Two storeys, the exit is only reachable over the stairs.
+const DUNGEON: &str = "\
+XXXXXX
+XS  #X
+XXXXXX
+
+XXXXXX
+XE  #X
+XXXXXX
+";
+
+#[test]
+fn levels_are_separated_by_blank_lines() {
+    let maze: LayeredMaze = DUNGEON.parse().unwrap();
+    assert_eq!((maze.width(), maze.height(), maze.depth()), (6, 3, 2));
+    assert_eq!(maze.start(), (1, 1, 0));
+    assert_eq!(maze.exits(), [(1, 1, 1)]);
+
+    assert_eq!(
+        maze.level_to_string(0).unwrap(),
+        "⬜⬜⬜⬜⬜⬜\n⬜❌⬛⬛🪜⬜\n⬜⬜⬜⬜⬜⬜\n"
+    );
+    assert_eq!(
+        maze.level_to_string(1).unwrap(),
+        "⬜⬜⬜⬜⬜⬜\n⬜🏁⬛⬛🪜⬜\n⬜⬜⬜⬜⬜⬜\n"
+    );
+    assert_eq!(maze.level_to_string(2), None);
+    assert_eq!(
+        maze.to_string(),
+        format!(
+            "{}\n{}",
+            maze.level_to_string(0).unwrap(),
+            maze.level_to_string(1).unwrap()
+        )
+    );
+}
+
+#[test]
+fn stairs_connect_the_levels() {
+    let maze: LayeredMaze = DUNGEON.parse().unwrap();
+    for algorithm in [
+        SolveAlgorithm::DepthFirst,
+        SolveAlgorithm::BreadthFirst,
+        SolveAlgorithm::Dijkstra,
+    ] {
+        assert_eq!(
+            maze.find_path_with(algorithm),
+            Ok(Some(vec![
+                (1, 1, 0),
+                (2, 1, 0),
+                (3, 1, 0),
+                (4, 1, 0),
+                (4, 1, 1),
+                (3, 1, 1),
+                (2, 1, 1),
+                (1, 1, 1),
+            ])),
+            "{:?}",
+            algorithm
+        );
+    }
+    assert_eq!(maze.find_cheapest_path().unwrap().unwrap().1, 7);
+
+    // stairs only lead to stairs
+    let maze: LayeredMaze = DUNGEON.replace("XE  #X", "XE # X").parse().unwrap();
+    assert_eq!(maze.find_path(), Ok(None));
+}
+
+#[test]
+fn stairs_pass_through_several_levels() {
+    let mut maze: LayeredMaze = "XXXX\nXS#X\nXXXX\n\nXXXX\nX #X\nXXXX\n\n\nXXXX\nX # \nXXXX"
+        .parse()
+        .unwrap();
+    assert_eq!(maze.depth(), 3);
+
+    // without explicit exits the borders of all levels are exits
+    let path = vec![(1, 1, 0), (2, 1, 0), (2, 1, 1), (2, 1, 2), (3, 1, 2)];
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(path.clone()))
+    );
+
+    // the stairs stay marked, so the solved maze is still solvable
+    assert_eq!(maze.solve(), Ok(true));
+    assert_eq!(
+        maze.level_to_string(1).unwrap(),
+        "⬜⬜⬜⬜\n⬜⬛🪜⬜\n⬜⬜⬜⬜\n"
+    );
+    assert_eq!(
+        maze.level_to_string(2).unwrap(),
+        "⬜⬜⬜⬜\n⬜⬛🪜👣\n⬜⬜⬜⬜\n"
+    );
+    assert_eq!(maze.find_path(), Ok(Some(path)));
+}
+
+#[test]
+fn invalid_layered_mazes_are_rejected() {
+    assert_eq!(
+        "XXX\nX X\nXXX\n\nXXX\nX X\nXXX"
+            .parse::<LayeredMaze>()
+            .unwrap_err(),
+        MazeError::MissingStart
+    );
+    assert_eq!(
+        "XXX\nXSX\nXXX\n\nXXX\nXSX\nXXX"
+            .parse::<LayeredMaze>()
+            .unwrap_err(),
+        on_level(1, MazeError::MultipleStarts { x: 1, y: 1 })
+    );
+    assert_eq!(
+        "XXX\nXSX\nXXX\n\nXXX\nX X\nXXX\n@start 1,1,1"
+            .parse::<LayeredMaze>()
+            .unwrap_err(),
+        on_level(1, MazeError::MultipleStarts { x: 1, y: 1 })
+    );
+    assert_eq!(
+        "XXX\nXSX\nXXX\n\nXXX\nXaX\nXXX"
+            .parse::<LayeredMaze>()
+            .unwrap_err(),
+        on_level(1, MazeError::UnpairedPortal { portal: 'a' })
+    );
+    assert_eq!(
+        "XXX\nXSX\nXXX\n\nXXX\nX X\nXXX\n@path 0,1,1"
+            .parse::<LayeredMaze>()
+            .unwrap_err(),
+        on_level(1, MazeError::PathOnWall { x: 0, y: 1 })
+    );
+    assert_eq!(
+        MazeError::MultipleStarts { x: 1, y: 1 }.to_string(),
+        "Second starting position at (1, 1)!"
+    );
+    assert_eq!(
+        on_level(1, MazeError::MultipleStarts { x: 1, y: 1 }).to_string(),
+        "Level 1: Second starting position at (1, 1)!"
+    );
+    // lines count from the start of the input, not of the level
+    assert_eq!(
+        "XXX\nXSX\nXXX\n\n\nXXX\nX?X\nXXX"
+            .parse::<LayeredMaze>()
+            .unwrap_err(),
+        MazeError::UnknownCharacter {
+            character: '?',
+            line: 7,
+            column: 2
+        }
+    );
+    assert_eq!(
+        LayeredMaze::new(vec![vec![vec![MazeCell::Wall; 3]; 3]], 1, 1, 0).unwrap_err(),
+        on_level(0, MazeError::StartOnWall { x: 1, y: 1 })
+    );
+    assert_eq!(
+        LayeredMaze::new(vec![vec![vec![MazeCell::Wall; 3]; 3]], 1, 1, 1).unwrap_err(),
+        on_level(1, MazeError::StartOutOfBounds { x: 1, y: 1 })
+    );
+    let maze: LayeredMaze = "XXX\nXSX\nXXX\n\nXXX\nX X\nXXX".parse().unwrap();
+    assert_eq!(
+        maze.clone()
+            .set_exits(vec![(1, 1, 1), (0, 0, 1)])
+            .unwrap_err(),
+        on_level(1, MazeError::ExitOnWall { x: 0, y: 0 })
+    );
+    assert_eq!(
+        maze.set_exits(vec![(1, 1, 2)]).unwrap_err(),
+        on_level(2, MazeError::ExitOutOfBounds { x: 1, y: 1 })
+    );
+    assert_eq!(
+        LayeredMaze::new(Vec::new(), 0, 0, 0).unwrap_err(),
+        MazeError::TooSmall {
+            width: 0,
+            height: 0
+        }
+    );
+}
+
+fn on_level(z: usize, error: MazeError) -> MazeError {
+    MazeError::OnLevel {
+        z,
+        error: Box::new(error),
+    }
+}
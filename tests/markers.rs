@@ -0,0 +1,65 @@
+use maze::{Maze, MazeError, Solvable, SolveAlgorithm};
+
+#[test]
+fn start_marker_sets_starting_position() {
+    let maze: Maze = "XXXXX\nX  SX\nX XXX\nXXXXX\n".parse().unwrap();
+    assert_eq!((maze.start_x(), maze.start_y()), (3, 1));
+    assert!(maze.exits().is_empty());
+    assert_eq!(maze.find_path(), Ok(None));
+}
+
+#[test]
+fn explicit_start_takes_precedence_over_marker() {
+    let maze = Maze::new_from_str("XXXXX\nX  SX\nX XXX\nXXXXX", 1, 1).unwrap();
+    assert_eq!((maze.start_x(), maze.start_y()), (1, 1));
+}
+
+#[test]
+fn exits_replace_the_border() {
+    let maze: Maze = "XXXXXXX\n S   E \nXXXXXXX".parse().unwrap();
+    assert_eq!(maze.exits(), &[(5, 1)]);
+
+    for algorithm in [SolveAlgorithm::DepthFirst, SolveAlgorithm::BreadthFirst] {
+        let path = maze.find_path_with(algorithm).unwrap().unwrap();
+        assert_eq!(path, vec![(1, 1), (2, 1), (3, 1), (4, 1), (5, 1)]);
+    }
+    assert_eq!(
+        maze.with_path(&[(1, 1), (2, 1)]).unwrap().to_string(),
+        "⬜⬜⬜⬜⬜⬜⬜\n⬛❌👣⬛⬛🏁⬛\n⬜⬜⬜⬜⬜⬜⬜\n"
+    );
+}
+
+#[test]
+fn nearest_of_several_exits_is_found() {
+    let maze: Maze = "XXXXXXXXX\nXE  S  EX\nX XXXXX X\nX   E   X\nXXXXXXXXX"
+        .parse()
+        .unwrap();
+    let path = maze
+        .find_path_with(SolveAlgorithm::BreadthFirst)
+        .unwrap()
+        .unwrap();
+    assert_eq!(path.len() - 1, 3);
+    let (_, stats) = maze
+        .find_path_astar(maze.exits(), Default::default())
+        .unwrap();
+    assert_eq!(stats.path_cost, Some(3));
+}
+
+#[test]
+fn invalid_markers_are_rejected() {
+    assert_eq!(
+        "XXX\nX X\nXXX".parse::<Maze>().unwrap_err(),
+        MazeError::MissingStart
+    );
+    assert_eq!(
+        "XXXX\nXSSX\nXXXX".parse::<Maze>().unwrap_err(),
+        MazeError::MultipleStarts { x: 2, y: 1 }
+    );
+    assert_eq!(
+        Maze::new_from_str("XXX\nX X\nXXX", 1, 1)
+            .unwrap()
+            .set_exits(vec![(0, 0)])
+            .unwrap_err(),
+        MazeError::ExitOnWall { x: 0, y: 0 }
+    );
+}
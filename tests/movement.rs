@@ -0,0 +1,88 @@
+use maze::{CornerCutting, Heuristic, Maze, Movement, Solvable, SolveAlgorithm};
+
+/// Open room with a pillar, the exit is in the opposite corner.
+fn room() -> Maze {
+    ["XXXXXX", "XS   X", "X X  X", "X   EX", "XXXXXX"]
+        .join("\n")
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn orthogonal_is_the_default() {
+    let maze = room();
+    assert_eq!(maze.movement(), Movement::Orthogonal);
+    let path = maze
+        .find_path_with(SolveAlgorithm::BreadthFirst)
+        .unwrap()
+        .unwrap();
+    assert_eq!(path.len() - 1, 5);
+}
+
+#[test]
+fn diagonal_steps_shorten_the_route() {
+    let maze = room().with_movement(Movement::Diagonal(CornerCutting::Never));
+
+    // (2, 1) -> (3, 2) would cut the corner of the pillar
+
+    let path = maze
+        .find_path_with(SolveAlgorithm::BreadthFirst)
+        .unwrap()
+        .unwrap();
+    assert_eq!(path, [(1, 1), (2, 1), (3, 1), (3, 2), (4, 3)]);
+
+    let (path, stats) = maze
+        .find_path_astar(&[(4, 3)], Heuristic::Chebyshev)
+        .unwrap();
+    assert_eq!(path.map(|path| path.len() - 1), Some(4));
+    assert_eq!(stats.path_cost, Some(4));
+
+    let (path, cost) = maze.find_cheapest_path().unwrap().unwrap();
+    assert_eq!((path.len() - 1, cost), (4, 4));
+
+    let path = maze.find_path().unwrap().unwrap();
+    assert_eq!(path.last(), Some(&(4, 3)));
+
+    let maze = maze.with_movement(Movement::Diagonal(CornerCutting::OneWall));
+    let path = maze
+        .find_path_with(SolveAlgorithm::BreadthFirst)
+        .unwrap()
+        .unwrap();
+    assert_eq!(path, [(1, 1), (2, 1), (3, 2), (4, 3)]);
+}
+
+#[test]
+fn corner_cutting_rules() {
+    // the diagonal step to the exit passes one wall
+    let one_wall: Maze = ["XXXX", "XS X", "XXEX", "XXXX"].join("\n").parse().unwrap();
+    // the diagonal step to the exit squeezes between two walls
+    let two_walls: Maze = ["XXXX", "XSXX", "XXEX", "XXXX"].join("\n").parse().unwrap();
+
+    for (corners, one_wall_steps, two_walls_steps) in [
+        (CornerCutting::Never, Some(2), None),
+        (CornerCutting::OneWall, Some(1), None),
+        (CornerCutting::Always, Some(1), Some(1)),
+    ] {
+        let movement = Movement::Diagonal(corners);
+        for (maze, steps) in [(&one_wall, one_wall_steps), (&two_walls, two_walls_steps)] {
+            let path = maze
+                .clone()
+                .with_movement(movement)
+                .find_path_with(SolveAlgorithm::BreadthFirst)
+                .unwrap();
+            assert_eq!(path.map(|path| path.len() - 1), steps, "{:?}", corners);
+        }
+    }
+}
+
+#[test]
+fn reachability_follows_the_movement() {
+    let maze: Maze = ["XXXXX", "XS XX", "XXX X", "XXXXX"]
+        .join("\n")
+        .parse()
+        .unwrap();
+    assert_eq!(maze.reachable_cells(), Ok(vec![(1, 1), (2, 1)]));
+
+    let maze = maze.with_movement(Movement::Diagonal(CornerCutting::Always));
+    assert_eq!(maze.reachable_cells(), Ok(vec![(1, 1), (2, 1), (3, 2)]));
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::corridor;
+use maze::{MazeError, Shape, Solvable, SolveAlgorithm};
+
+/// Kinds and data of the chunks after the signature.
+fn chunks(png: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let mut chunks = Vec::new();
+    let mut rest = &png[8..];
+    while !rest.is_empty() {
+        let length = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+        let kind = String::from_utf8(rest[4..8].to_vec()).unwrap();
+        chunks.push((kind, rest[8..8 + length].to_vec()));
+        rest = &rest[12 + length..];
+    }
+    chunks
+}
+
+#[test]
+fn png_has_the_size_of_the_cells() {
+    let maze = corridor();
+    let path = maze
+        .find_path_with(SolveAlgorithm::BreadthFirst)
+        .unwrap()
+        .unwrap();
+    let png = maze.to_png(4, &path).unwrap();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+    let parts = chunks(&png);
+    let kinds: Vec<&str> = parts.iter().map(|(kind, _)| kind.as_str()).collect();
+    assert_eq!(kinds, ["IHDR", "IDAT", "IEND"]);
+    // 20 x 12 pixels, 8 bit RGB
+    assert_eq!(parts[0].1, [0, 0, 0, 20, 0, 0, 0, 12, 8, 2, 0, 0, 0]);
+    // zlib stream
+    assert_eq!(parts[1].1[..2], [0x78, 0x01]);
+
+    // odd rows are shifted by half a cell
+    let png = maze.clone().with_shape(Shape::Hex).to_png(4, &[]).unwrap();
+    assert_eq!(chunks(&png)[0].1[..8], [0, 0, 0, 22, 0, 0, 0, 12]);
+}
+
+#[test]
+fn png_is_compressed_and_deterministic() {
+    let maze = corridor();
+    let png = maze.to_png(50, &[]).unwrap();
+    // 250 x 150 pixels are 112500 bytes uncompressed
+    assert!(png.len() < 2000, "{}", png.len());
+    assert_eq!(png, maze.to_png(50, &[]).unwrap());
+    assert_ne!(png, maze.to_png(50, &[(1, 1), (2, 1)]).unwrap());
+}
+
+#[test]
+fn png_rejects_invalid_input() {
+    let maze = corridor();
+    assert_eq!(
+        maze.to_png(0, &[]),
+        Err(MazeError::InvalidCellSize { size: 0 })
+    );
+    assert_eq!(
+        maze.to_png(1, &[(0, 0)]),
+        Err(MazeError::PathOnWall { x: 0, y: 0 })
+    );
+    assert_eq!(
+        maze.to_png(1, &[(5, 1)]),
+        Err(MazeError::PathOutOfBounds { x: 5, y: 1 })
+    );
+    // wider than PNG allows, and too large to compute at all
+    for cell_size in [1 << 30, 5_000_000_000, usize::MAX] {
+        assert_eq!(
+            maze.to_png(cell_size, &[]),
+            Err(MazeError::ImageTooLarge { cell_size })
+        );
+    }
+}
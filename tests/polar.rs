@@ -0,0 +1,94 @@
+use maze::{MazeError, PolarBacktracker, PolarMaze, Solvable, SolveAlgorithm};
+
+/// Number of open walls, counting each passage once.
+fn passages(maze: &PolarMaze) -> usize {
+    (1..maze.rings())
+        .map(|ring| {
+            let size = maze.cells_in_ring(ring).unwrap();
+            let ratio = size / maze.cells_in_ring(ring - 1).unwrap();
+            (0..size)
+                .filter(|&cell| maze.is_linked((cell, ring), ((cell + 1) % size, ring)))
+                .count()
+                + (0..size)
+                    .filter(|&cell| maze.is_linked((cell, ring), (cell / ratio, ring - 1)))
+                    .count()
+        })
+        .sum()
+}
+
+#[test]
+fn rings_grow_outward() {
+    let maze = PolarBacktracker::new(1).generate(6).unwrap();
+    assert_eq!(maze.rings(), 6);
+    let sizes: Vec<usize> = (0..6)
+        .map(|ring| maze.cells_in_ring(ring).unwrap())
+        .collect();
+    assert_eq!(sizes, [1, 6, 12, 24, 24, 24]);
+    assert_eq!(maze.cells_in_ring(6), None);
+    assert_eq!(maze.start(), (0, 0));
+    assert_eq!(maze.exit().1, 5);
+}
+
+#[test]
+fn generated_mazes_are_perfect() {
+    for seed in 0..20 {
+        let maze = PolarBacktracker::new(seed).generate(8).unwrap();
+        let cells: usize = (0..maze.rings())
+            .map(|ring| maze.cells_in_ring(ring).unwrap())
+            .sum();
+        assert_eq!(passages(&maze), cells - 1);
+
+        // a perfect maze has exactly one route, every solver finds it
+        let path = maze
+            .find_path_with(SolveAlgorithm::BreadthFirst)
+            .unwrap()
+            .expect("maze has an exit");
+        assert_eq!(path.first(), Some(&maze.start()));
+        assert_eq!(path.last(), Some(&maze.exit()));
+        for pair in path.windows(2) {
+            assert!(maze.is_linked(pair[0], pair[1]));
+        }
+        assert_eq!(maze.find_path(), Ok(Some(path.clone())));
+        assert_eq!(
+            maze.find_path_with(SolveAlgorithm::Dijkstra),
+            Ok(Some(path))
+        );
+    }
+
+    assert_eq!(
+        PolarBacktracker::new(0).generate(1).unwrap_err(),
+        MazeError::TooFewRings { rings: 1 }
+    );
+}
+
+#[test]
+fn generation_is_deterministic() {
+    let first = PolarBacktracker::new(7).generate(10).unwrap();
+    let second = PolarBacktracker::new(7).generate(10).unwrap();
+    assert_eq!(first.to_svg(), second.to_svg());
+    assert_eq!(first.exit(), second.exit());
+
+    let other = PolarBacktracker::new(8).generate(10).unwrap();
+    assert_ne!(first.to_svg(), other.to_svg());
+}
+
+#[test]
+fn solving_marks_the_route_in_the_svg() {
+    let mut maze = PolarBacktracker::new(3).generate(5).unwrap();
+    assert!(maze.path().is_empty());
+    let svg = maze.to_svg();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(!svg.contains("<polyline"));
+    assert_eq!(svg.matches("<circle ").count(), 2);
+
+    assert_eq!(maze.solve(), Ok(true));
+    let path = maze.path().to_vec();
+    assert_eq!(path.last(), Some(&maze.exit()));
+    let svg = maze.to_svg();
+    let polyline = svg
+        .lines()
+        .find(|line| line.starts_with("<polyline"))
+        .unwrap();
+    // one point per cell and one outside of the exit
+    assert_eq!(polyline.matches(',').count(), path.len() + 1);
+}
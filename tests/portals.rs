@@ -0,0 +1,126 @@
+use maze::{
+    FloorType, Heuristic, LayeredMaze, Maze, MazeCell, MazeError, Solvable, SolveAlgorithm,
+};
+
+/// Walking right to the exit is longer than jumping from the portal left of the start.
+fn shortcut() -> Maze {
+    ["XXXXXXXXX", "XaS   aEX", "XXXXXXXXX"]
+        .join("\n")
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn portals_are_parsed_in_pairs() {
+    let maze = shortcut();
+    assert_eq!(maze.portal(1, 1), Some((6, 1)));
+    assert_eq!(maze.portal(6, 1), Some((1, 1)));
+    assert_eq!(maze.portal(2, 1), None);
+    assert_eq!(
+        maze.to_string(),
+        "⬜⬜⬜⬜⬜⬜⬜⬜⬜\n⬜🌀❌⬛⬛⬛🌀🏁⬜\n⬜⬜⬜⬜⬜⬜⬜⬜⬜\n"
+    );
+
+    assert_eq!(
+        "XXXX\nXSaE\nXXXX".parse::<Maze>().unwrap_err(),
+        MazeError::UnpairedPortal { portal: 'a' }
+    );
+    assert_eq!(
+        "XXXXXX\nXSbbbE\nXXXXXX".parse::<Maze>().unwrap_err(),
+        MazeError::UnpairedPortal { portal: 'b' }
+    );
+
+    let portal = MazeCell::Floor(FloorType::Portal('z'));
+    assert_eq!(
+        Maze::new(
+            vec![
+                vec![MazeCell::Wall; 3],
+                vec![portal.clone(), MazeCell::Floor(FloorType::Floor), portal],
+                vec![MazeCell::Wall; 3]
+            ],
+            1,
+            1
+        )
+        .map(|maze| maze.portal(0, 1)),
+        Ok(Some((2, 1)))
+    );
+}
+
+#[test]
+fn solvers_jump_through_portals() {
+    let maze = shortcut();
+    let jump = vec![(2, 1), (1, 1), (6, 1), (7, 1)];
+
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(jump.clone()))
+    );
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::Dijkstra),
+        Ok(Some(jump.clone()))
+    );
+    assert_eq!(maze.find_cheapest_path(), Ok(Some((jump.clone(), 2))));
+    assert_eq!(maze.path_cost(&jump), Ok(2));
+    assert!(maze.find_path().unwrap().is_some());
+
+    // estimates towards the goal alone would make the long walk look cheaper
+    for heuristic in [
+        Heuristic::Manhattan,
+        Heuristic::Chebyshev,
+        Heuristic::Euclidean,
+        Heuristic::Zero,
+    ] {
+        let (path, stats) = maze.find_path_astar(&[(7, 1)], heuristic).unwrap();
+        assert_eq!(path.as_ref(), Some(&jump), "{:?}", heuristic);
+        assert_eq!(stats.path_cost, Some(2));
+    }
+}
+
+#[test]
+fn marked_path_shows_the_jump() {
+    let mut maze: Maze = "XXXXXXXX\nXS a XaE\nXXXXXXXX".parse().unwrap();
+    assert_eq!(maze.solve_with(SolveAlgorithm::BreadthFirst), Ok(true));
+    assert_eq!(
+        maze.to_string(),
+        "⬜⬜⬜⬜⬜⬜⬜⬜\n⬜❌👣🌀⬛⬜🌀🏁\n⬜⬜⬜⬜⬜⬜⬜⬜\n"
+    );
+    // the portals still work after marking
+    assert_eq!(maze.portal(3, 1), Some((6, 1)));
+}
+
+#[test]
+fn layered_levels_have_their_own_portals() {
+    let maze: LayeredMaze = [
+        "XXXXXX", "XSa a#", "XXXXXX", "", "XXXXXX", "XEb b#", "XXXXXX",
+    ]
+    .join("\n")
+    .parse()
+    .unwrap();
+    assert_eq!(
+        maze.find_cheapest_path(),
+        Ok(Some((
+            vec![
+                (1, 1, 0),
+                (2, 1, 0),
+                (4, 1, 0),
+                (5, 1, 0),
+                (5, 1, 1),
+                (4, 1, 1),
+                (2, 1, 1),
+                (1, 1, 1)
+            ],
+            5
+        )))
+    );
+
+    assert_eq!(
+        ["XXXX", "XSaX", "XXXX", "", "XXXX", "XaEX", "XXXX"]
+            .join("\n")
+            .parse::<LayeredMaze>()
+            .unwrap_err(),
+        MazeError::OnLevel {
+            z: 0,
+            error: Box::new(MazeError::UnpairedPortal { portal: 'a' })
+        }
+    );
+}
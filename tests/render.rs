@@ -0,0 +1,242 @@
+use maze::{
+    Generator, LayeredMaze, Maze, MazeError, RecursiveBacktracker, RenderStyle, Shape, Solvable,
+    SolveAlgorithm,
+};
+
+fn solved() -> Maze {
+    let mut maze: Maze = "XXXXXXX\nXS..%=X\nX XXX.E\nX   X X\nXXXXXXX"
+        .parse()
+        .unwrap();
+    assert_eq!(maze.solve_with(SolveAlgorithm::BreadthFirst), Ok(true));
+    maze
+}
+
+#[test]
+fn ascii_uses_the_input_format() {
+    assert_eq!(
+        solved().to_string_with(RenderStyle::Ascii),
+        "XXXXXXX\nXS..%=X\nX XXX.E\nX   X X\nXXXXXXX\n@path 4,1 5,1\n"
+    );
+
+    let special: Maze = "XXXXXXXX\nXSaA<6*X\nXb#~2vBE\nXXXXXXXX".parse().unwrap();
+    assert_eq!(
+        special.to_string_with(RenderStyle::Ascii),
+        "XXXXXXXX\nXSaA<6*X\nXb#~2vBE\nXXXXXXXX\n"
+    );
+
+    // the default is the Display output
+    assert_eq!(
+        solved().to_string_with(RenderStyle::default()),
+        solved().to_string()
+    );
+}
+
+#[test]
+fn box_drawing_joins_walls() {
+    assert_eq!(
+        solved().to_string_with(RenderStyle::BoxDrawing),
+        "┌─────┐\n│S..%=│\n│ ──┐.E\n│   │ │\n└───┴─┘\n"
+    );
+
+    // walls without neighbouring walls
+    let pillar: Maze = "XXXXX\nXS  X\nX X E\nX   X\nXXXXX".parse().unwrap();
+    assert_eq!(
+        pillar.to_string_with(RenderStyle::BoxDrawing),
+        "┌───┐\n│S  │\n│ ▪ E\n│   │\n└───┘\n"
+    );
+}
+
+#[test]
+fn half_blocks_show_two_rows_per_line() {
+    assert_eq!(
+        solved().to_string_with(RenderStyle::HalfBlock),
+        "█S..%=█\n█ ▀▀█.E\n▀▀▀▀▀▀▀\n"
+    );
+
+    // hex mazes are not shifted
+    assert_eq!(
+        solved()
+            .with_shape(Shape::Hex)
+            .to_string_with(RenderStyle::HalfBlock),
+        "█S..%=█\n█ ▀▀█.E\n▀▀▀▀▀▀▀\n"
+    );
+}
+
+#[test]
+fn layered_mazes_render_all_levels() {
+    let maze: LayeredMaze = ["XXXX", "XS#X", "XXXX", "", "XXXX", "XE#X", "XXXX"]
+        .join("\n")
+        .parse()
+        .unwrap();
+    assert_eq!(
+        maze.to_string_with(RenderStyle::Ascii),
+        "XXXX\nXS#X\nXXXX\n\nXXXX\nXE#X\nXXXX\n"
+    );
+    assert_eq!(maze.to_string_with(RenderStyle::Emoji), maze.to_string());
+}
+
+#[test]
+fn ascii_output_reads_back() {
+    let marked: Maze = "XXXXX\nXS.EX\nXXXXX".parse().unwrap();
+    assert_eq!(marked.to_string(), "⬜⬜⬜⬜⬜\n⬜❌👣🏁⬜\n⬜⬜⬜⬜⬜\n");
+
+    let special: Maze = [
+        "XXXXXXXXXX",
+        "XS =%~#* X",
+        "X XXXXXXaX",
+        "X <>^v46 X",
+        "X XXXXXXXX",
+        "Xa bB 82 E",
+        "XXXXXXXXXX",
+    ]
+    .join("\n")
+    .parse::<Maze>()
+    .and_then(|maze| maze.with_path(&[(1, 1), (1, 2), (1, 3), (1, 4), (1, 5), (2, 5)]))
+    .unwrap();
+    let text = special.to_string_with(RenderStyle::Ascii);
+    let read: Maze = text.parse().unwrap();
+    assert_eq!(read.to_string_with(RenderStyle::Ascii), text);
+    // the trailing newline is not an extra row
+    let read_at = Maze::new_from_str(&text, 1, 1).unwrap();
+    assert_eq!((read_at.width(), read_at.height()), (10, 7));
+    assert_eq!(read_at.to_string_with(RenderStyle::Ascii), text);
+    assert_eq!(read.to_string(), special.to_string());
+    assert_eq!(read.exits(), special.exits());
+    assert_eq!(read.portal(8, 2), Some((1, 5)));
+
+    for seed in 0..5 {
+        let mut maze = RecursiveBacktracker::new(seed).generate(7, 5).unwrap();
+        assert_eq!(maze.solve(), Ok(true));
+        let text = maze.to_string_with(RenderStyle::Ascii);
+        let read: Maze = text.parse().unwrap();
+        assert_eq!(read.to_string_with(RenderStyle::Ascii), text);
+        assert_eq!(read.to_string(), maze.to_string());
+    }
+
+    let mut layered: LayeredMaze = ["XXXXX", "XS #X", "XXXXX", "", "XXXXX", "XE #X", "XXXXX"]
+        .join("\n")
+        .parse()
+        .unwrap();
+    assert_eq!(layered.solve(), Ok(true));
+    let text = layered.to_string_with(RenderStyle::Ascii);
+    assert_eq!(
+        text,
+        "XXXXX\nXS.#X\nXXXXX\n\nXXXXX\nXE.#X\nXXXXX\n@path 3,1,0 3,1,1\n"
+    );
+    let read: LayeredMaze = text.parse().unwrap();
+    assert_eq!(read.to_string_with(RenderStyle::Ascii), text);
+}
+
+#[test]
+fn markers_on_special_floor_are_listed_after_the_rows() {
+    let maze: Maze = [
+        "XXXXXXXXXXXXX",
+        "XS=%~*#a<6b X",
+        "Xa  B      bX",
+        "XXXXXXXXXXXXX",
+    ]
+    .join("\n")
+    .parse()
+    .unwrap();
+    for x in 2..=10 {
+        let moved = maze
+            .clone()
+            .set_start(x, 1)
+            .and_then(|maze| maze.set_exits(vec![(x, 1), (11, 2), (1, 1)]))
+            .unwrap();
+        let text = moved.to_string_with(RenderStyle::Ascii);
+        // the cell keeps its character, the markers can't hide it
+        assert!(text.starts_with("XXXXXXXXXXXXX\nXE=%~*#a<6b X\nXa  B      bX\n"));
+        assert!(text.ends_with(&format!("XXXXXXXXXXXXX\n@start {x},1\n@exits {x},1 11,2\n")));
+
+        let read: Maze = text.parse().unwrap();
+        assert_eq!(read.to_string_with(RenderStyle::Ascii), text);
+        assert_eq!((read.start_x(), read.start_y()), (x, 1));
+        assert_eq!(read.exits(), [(1, 1), (x, 1), (11, 2)]);
+        assert_eq!(read.portal(7, 1), Some((1, 2)));
+    }
+
+    // an exit on the start is listed too
+    let on_start = maze.set_exits(vec![(1, 1)]).unwrap();
+    let text = on_start.to_string_with(RenderStyle::Ascii);
+    assert!(text.ends_with("XXXXXXXXXXXXX\n@exits 1,1\n"));
+    let read: Maze = text.parse().unwrap();
+    assert_eq!(read.exits(), [(1, 1)]);
+    assert_eq!(read.to_string_with(RenderStyle::Ascii), text);
+
+    let layered: LayeredMaze = ["XXXX", "XS#X", "XXXX", "", "XXXX", "X #X", "XXXX"]
+        .join("\n")
+        .parse()
+        .unwrap();
+    let text = layered
+        .set_exits(vec![(2, 1, 1)])
+        .unwrap()
+        .to_string_with(RenderStyle::Ascii);
+    assert_eq!(text, "XXXX\nXS#X\nXXXX\n\nXXXX\nX #X\nXXXX\n@exits 2,1,1\n");
+    let read: LayeredMaze = text.parse().unwrap();
+    assert_eq!(read.exits(), [(2, 1, 1)]);
+    assert_eq!(read.to_string_with(RenderStyle::Ascii), text);
+}
+
+#[test]
+fn path_keeps_the_floor_below() {
+    let mut maze: Maze = "XXXXXXXXX\nXS =%~#*E\nXXXXXXXXX".parse().unwrap();
+    let cheapest = maze.find_cheapest_path().unwrap();
+    assert_eq!(maze.solve_with(SolveAlgorithm::BreadthFirst), Ok(true));
+    let text = maze.to_string_with(RenderStyle::Ascii);
+    assert_eq!(
+        text,
+        "XXXXXXXXX\nXS.=%~#*E\nXXXXXXXXX\n@path 3,1 4,1 5,1 6,1\n"
+    );
+
+    let read: Maze = text.parse().unwrap();
+    assert_eq!(read.to_string_with(RenderStyle::Ascii), text);
+    assert_eq!(read.find_cheapest_path(), Ok(cheapest.clone()));
+    assert_eq!(maze.find_cheapest_path(), Ok(cheapest));
+
+    // on every kind of special floor, over several levels
+    let mut layered: LayeredMaze = [
+        "XXXXXXX", "XS=>6#X", "XXXXXXX", "", "XXXXXXX", "XE%~*#X", "XXXXXXX",
+    ]
+    .join("\n")
+    .parse()
+    .unwrap();
+    assert_eq!(layered.solve(), Ok(true));
+    let text = layered.to_string_with(RenderStyle::Ascii);
+    assert_eq!(
+        text,
+        "XXXXXXX\nXS=>6#X\nXXXXXXX\n\nXXXXXXX\nXE%~*#X\nXXXXXXX\n\
+         @path 2,1,0 3,1,0 4,1,0 5,1,0 2,1,1 3,1,1 5,1,1\n"
+    );
+    let read: LayeredMaze = text.parse().unwrap();
+    assert_eq!(read.to_string_with(RenderStyle::Ascii), text);
+}
+
+#[test]
+fn invalid_marker_lines_are_rejected() {
+    let rows = "XXXXX\nXS =X\nXXXXX\n";
+    for (markers, error) in [
+        ("@path 3", MazeError::InvalidMarkers { line: 4 }),
+        ("@path 3,1,0", MazeError::InvalidMarkers { line: 4 }),
+        (
+            "@exits 3,1\n@walls 1,1",
+            MazeError::InvalidMarkers { line: 5 },
+        ),
+        ("@exits 3,1\nXXXXX", MazeError::InvalidMarkers { line: 5 }),
+        ("@start 1,1 3,1", MazeError::InvalidMarkers { line: 4 }),
+        (
+            "@start 3,1\n@start 3,1",
+            MazeError::InvalidMarkers { line: 5 },
+        ),
+        ("@start 3,1", MazeError::MultipleStarts { x: 3, y: 1 }),
+        ("@path 4,1", MazeError::PathOnWall { x: 4, y: 1 }),
+        ("@path 3,5", MazeError::PathOutOfBounds { x: 3, y: 5 }),
+    ] {
+        assert_eq!(
+            format!("{}{}", rows, markers).parse::<Maze>().unwrap_err(),
+            error,
+            "{}",
+            markers
+        );
+    }
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::corridor;
+use maze::{Maze, PolarBacktracker, Solvable, SolveAlgorithm, SvgStyle, Topology};
+
+#[test]
+fn style_sets_sizes_and_colours() {
+    let style = SvgStyle::default()
+        .with_cell_size(10.0)
+        .with_margin(5.0)
+        .with_wall_colour("black")
+        .with_floor_colour("white")
+        .with_start_colour("green")
+        .with_exit_colour("red");
+    let svg = corridor().to_svg_with(&style, &[]);
+    assert!(svg.starts_with(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"60.00\" height=\"40.00\" \
+         viewBox=\"0 0 60.00 40.00\">\n"
+    ));
+    assert!(svg
+        .contains("<rect x=\"5.00\" y=\"5.00\" width=\"10.00\" height=\"10.00\" fill=\"black\"/>"));
+    assert!(svg.contains(
+        "<rect x=\"15.00\" y=\"15.00\" width=\"10.00\" height=\"10.00\" fill=\"green\"/>"
+    ));
+    assert!(svg.contains(
+        "<rect x=\"25.00\" y=\"15.00\" width=\"10.00\" height=\"10.00\" fill=\"white\"/>"
+    ));
+    assert!(svg
+        .contains("<rect x=\"35.00\" y=\"15.00\" width=\"10.00\" height=\"10.00\" fill=\"red\"/>"));
+    assert!(!svg.contains("<polyline"));
+
+    // the defaults are unchanged
+    assert_eq!(
+        corridor().to_svg(),
+        corridor().to_svg_with(&SvgStyle::default(), &[])
+    );
+}
+
+#[test]
+fn colours_cannot_break_out_of_their_attribute() {
+    let style = SvgStyle::default()
+        .with_wall_colour("red\" onload=\"alert(1)")
+        .with_path_colour("<script>&'");
+    let maze = corridor();
+    let svg = maze.to_svg_with(&style, &[(1, 1), (2, 1)]);
+    assert!(svg.contains("fill=\"red&quot; onload=&quot;alert(1)\"/>"));
+    assert!(svg.contains("stroke=\"&lt;script&gt;&amp;&apos;\""));
+    assert!(!svg.contains("onload=\""));
+    assert!(!svg.contains("<script>"));
+
+    let mut polar = PolarBacktracker::new(3).generate(3).unwrap();
+    assert_eq!(polar.solve(), Ok(true));
+    let svg = polar.to_svg_with(&style);
+    assert!(svg.contains("stroke=\"red&quot; onload=&quot;alert(1)\""));
+    assert!(!svg.contains("<script>"));
+}
+
+#[test]
+fn solution_is_drawn_as_a_polyline() {
+    let maze = corridor();
+    let path = maze
+        .find_path_with(SolveAlgorithm::BreadthFirst)
+        .unwrap()
+        .unwrap();
+    let style = SvgStyle::default()
+        .with_path_colour("orange")
+        .with_path_width(1.5);
+    let svg = maze.to_svg_with(&style, &path);
+    assert!(svg.ends_with(
+        "<polyline points=\"30.00,30.00 50.00,30.00 70.00,30.00\" fill=\"none\" \
+         stroke=\"orange\" stroke-width=\"1.5\" stroke-linecap=\"round\" \
+         stroke-linejoin=\"round\"/>\n</svg>\n"
+    ));
+}
+
+#[test]
+fn polyline_is_interrupted_by_jumps() {
+    let maze: Maze = "XXXXXXXX\nXS a XaE\nXXXXXXXX".parse().unwrap();
+    let path = maze
+        .find_path_with(SolveAlgorithm::BreadthFirst)
+        .unwrap()
+        .unwrap();
+    assert_eq!(path, vec![(1, 1), (2, 1), (3, 1), (6, 1), (7, 1)]);
+    let svg = maze.to_svg_with(&SvgStyle::default(), &path);
+    let polylines: Vec<&str> = svg
+        .lines()
+        .filter(|line| line.starts_with("<polyline"))
+        .collect();
+    assert_eq!(polylines.len(), 2);
+    assert!(polylines[0].contains("points=\"30.00,30.00 50.00,30.00 70.00,30.00\""));
+    assert!(polylines[1].contains("points=\"130.00,30.00 150.00,30.00\""));
+
+    let torus = Maze::new_from_str("XXXX\n S E\nXXXX", 1, 1)
+        .unwrap()
+        .with_topology(Topology::Torus)
+        .set_exits(vec![(3, 1)])
+        .unwrap();
+    let svg = torus.to_svg_with(&SvgStyle::default(), &[(1, 1), (0, 1), (3, 1)]);
+    assert_eq!(svg.matches("<polyline").count(), 1);
+    assert!(svg.contains("points=\"30.00,30.00 10.00,30.00\""));
+}
+
+#[test]
+fn polar_style_and_margin() {
+    let mut maze = PolarBacktracker::new(3).generate(4).unwrap();
+    assert_eq!(maze.solve(), Ok(true));
+    let style = SvgStyle::default()
+        .with_cell_size(10.0)
+        .with_margin(2.0)
+        .with_wall_width(1.0)
+        .with_path_colour("blue");
+    let svg = maze.to_svg_with(&style);
+    // 4 rings and half a ring of space on each side
+    assert!(svg.contains("width=\"94.00\" height=\"94.00\""));
+    assert!(svg.contains("stroke-width=\"1\""));
+    assert!(svg.contains("stroke=\"blue\""));
+    assert_eq!(svg.matches("r=\"2.50\"").count(), 2);
+}
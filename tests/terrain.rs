@@ -0,0 +1,87 @@
+use maze::{FloorType, Heuristic, Maze, MazeError, Solvable, SolveAlgorithm};
+
+/// The direct route leads through water, the detour follows a road.
+fn river_maze() -> Maze {
+    ["XXXXXXX", "XS~~~EX", "X=X X=X", "X=====X", "XXXXXXX"]
+        .join("\n")
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn terrain_is_parsed_and_displayed() {
+    let maze: Maze = "XXXXX\nXS=%X\nX~  E\nXXXXX".parse().unwrap();
+    assert_eq!(
+        maze.to_string(),
+        "⬜⬜⬜⬜⬜\n⬜❌🟨🟫⬜\n⬜🟦⬛⬛🏁\n⬜⬜⬜⬜⬜\n"
+    );
+}
+
+#[test]
+fn terrain_costs() {
+    assert_eq!(FloorType::Floor.cost(), 1);
+    assert!(FloorType::Floor.cost() < FloorType::Road.cost());
+    assert!(FloorType::Road.cost() < FloorType::Mud.cost());
+    assert!(FloorType::Mud.cost() < FloorType::Water.cost());
+    assert_eq!(FloorType::Start.cost(), FloorType::Floor.cost());
+}
+
+#[test]
+fn cheapest_path_avoids_expensive_terrain() {
+    let maze = river_maze();
+
+    let (path, cost) = maze.find_cheapest_path().unwrap().unwrap();
+    assert_eq!(
+        path,
+        [
+            (1, 1),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (3, 3),
+            (4, 3),
+            (5, 3),
+            (5, 2),
+            (5, 1)
+        ]
+    );
+    assert_eq!(cost, 15);
+    assert_eq!(maze.path_cost(&path), Ok(cost));
+
+    // fewest steps, but through the water
+    let shortest = maze
+        .find_path_with(SolveAlgorithm::BreadthFirst)
+        .unwrap()
+        .unwrap();
+    assert_eq!(shortest.len(), 5);
+    assert_eq!(maze.path_cost(&shortest), Ok(31));
+
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::Dijkstra),
+        Ok(Some(path))
+    );
+}
+
+#[test]
+fn astar_is_cost_aware() {
+    let maze = river_maze();
+    for heuristic in [Heuristic::Manhattan, Heuristic::Zero] {
+        let (path, stats) = maze.find_path_astar(&[(5, 1)], heuristic).unwrap();
+        assert_eq!(path.map(|path| path.len()), Some(9));
+        assert_eq!(stats.path_cost, Some(15));
+    }
+}
+
+#[test]
+fn path_cost_rejects_invalid_paths() {
+    let maze = river_maze();
+    assert_eq!(
+        maze.path_cost(&[(1, 1), (2, 2)]),
+        Err(MazeError::PathOnWall { x: 2, y: 2 })
+    );
+    assert_eq!(
+        maze.path_cost(&[(9, 1)]),
+        Err(MazeError::PathOutOfBounds { x: 9, y: 1 })
+    );
+    assert_eq!(maze.path_cost(&[]), Ok(0));
+}
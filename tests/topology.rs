@@ -0,0 +1,198 @@
+mod common;
+
+use common::generators;
+use maze::{
+    CornerCutting, Generator, Heuristic, Kruskal, Maze, MazeError, Movement, RecursiveBacktracker,
+    Shape, Solvable, SolveAlgorithm, Topology,
+};
+
+/// Corridor that is blocked in the middle but open at both ends of the row.
+fn corridor() -> Maze {
+    ["XXXXXX", " S X E", "XXXXXX"]
+        .join("\n")
+        .parse::<Maze>()
+        .unwrap()
+}
+
+#[test]
+fn torus_wraps_around_the_edges() {
+    let maze = corridor();
+    assert_eq!(maze.topology(), Topology::Bounded);
+    assert_eq!(maze.find_path(), Ok(None));
+
+    let torus = maze.with_topology(Topology::Torus);
+    assert_eq!(torus.topology(), Topology::Torus);
+    for algorithm in [
+        SolveAlgorithm::DepthFirst,
+        SolveAlgorithm::BreadthFirst,
+        SolveAlgorithm::Dijkstra,
+    ] {
+        assert_eq!(
+            torus.find_path_with(algorithm),
+            Ok(Some(vec![(1, 1), (0, 1), (5, 1)])),
+            "{:?}",
+            algorithm
+        );
+    }
+    assert_eq!(
+        torus.find_cheapest_path(),
+        Ok(Some((vec![(1, 1), (0, 1), (5, 1)], 2)))
+    );
+}
+
+#[test]
+fn torus_wraps_diagonally_and_vertically() {
+    let maze = ["XXX ", "XS X", "XXXX", "XXX "]
+        .join("\n")
+        .parse::<Maze>()
+        .unwrap()
+        .with_topology(Topology::Torus)
+        .set_exits(vec![(3, 3)])
+        .unwrap();
+    assert_eq!(maze.find_path(), Ok(None));
+
+    // (2, 1) touches (3, 0) which wraps to (3, 3) through the top
+    let maze = maze.with_movement(Movement::Diagonal(CornerCutting::Always));
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(vec![(1, 1), (2, 1), (3, 0), (3, 3)]))
+    );
+}
+
+#[test]
+fn torus_needs_explicit_exits() {
+    let maze = Maze::new_from_str("XXXXX\n     \nXXXXX", 1, 1)
+        .unwrap()
+        .with_topology(Topology::Torus);
+    assert_eq!(maze.find_path(), Err(MazeError::MissingExits));
+    assert_eq!(maze.find_cheapest_path(), Err(MazeError::MissingExits));
+
+    let maze = maze.set_exits(vec![(4, 1)]).unwrap();
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(vec![(1, 1), (0, 1), (4, 1)]))
+    );
+}
+
+#[test]
+fn hex_torus_needs_an_even_height() {
+    // open hex torus, the exit is up-left of the start across both edges
+    let open = |height: usize| {
+        Maze::new_from_str(&vec!["    "; height].join("\n"), 0, 0)
+            .unwrap()
+            .with_shape(Shape::Hex)
+            .with_topology(Topology::Torus)
+            .set_exits(vec![(3, height - 1)])
+            .unwrap()
+    };
+
+    let maze = open(4);
+    assert_eq!(
+        maze.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(vec![(0, 0), (3, 3)]))
+    );
+    // and the way back is a single step too
+    let back = maze
+        .set_start(3, 3)
+        .unwrap()
+        .set_exits(vec![(0, 0)])
+        .unwrap();
+    assert_eq!(
+        back.find_path_with(SolveAlgorithm::BreadthFirst),
+        Ok(Some(vec![(3, 3), (0, 0)]))
+    );
+
+    // with 3 rows the last one is not shifted, so the edges don't line up
+    let maze = open(3);
+    let odd = MazeError::OddHexTorus { height: 3 };
+    assert_eq!(maze.find_path().unwrap_err(), odd);
+    assert_eq!(maze.find_cheapest_path().unwrap_err(), odd);
+    assert_eq!(maze.reachable_cells().unwrap_err(), odd);
+    assert_eq!(maze.trap_cells().unwrap_err(), odd);
+    assert_eq!(maze.find_path_with_keys().unwrap_err(), odd);
+    assert_eq!(
+        maze.find_path_astar(&[(3, 2)], Heuristic::Zero)
+            .unwrap_err(),
+        odd
+    );
+    // only the combination is rejected
+    assert!(maze.clone().with_shape(Shape::Square).find_path().is_ok());
+    assert!(maze.with_topology(Topology::Bounded).find_path().is_ok());
+}
+
+#[test]
+fn astar_estimates_the_distance_around_the_torus() {
+    let maze = corridor().with_topology(Topology::Torus);
+    for heuristic in [
+        Heuristic::Manhattan,
+        Heuristic::Chebyshev,
+        Heuristic::Euclidean,
+        Heuristic::Zero,
+    ] {
+        let (path, stats) = maze.find_path_astar(&[(5, 1)], heuristic).unwrap();
+        assert_eq!(path, Some(vec![(1, 1), (0, 1), (5, 1)]), "{:?}", heuristic);
+        assert_eq!(stats.path_cost, Some(2));
+    }
+}
+
+#[test]
+fn generators_create_perfect_torus_mazes() {
+    let mut wrapping = 0;
+    for seed in 0..5 {
+        for (index, generator) in generators(seed).into_iter().enumerate() {
+            for (width, height) in [(8, 6), (2, 5), (3, 3)] {
+                let maze = generator
+                    .generate_with_topology(width, height, Topology::Torus)
+                    .unwrap();
+                assert_eq!((maze.width(), maze.height()), (2 * width, 2 * height));
+                assert_eq!(maze.topology(), Topology::Torus);
+                assert_eq!(maze.exits(), &[(2 * width - 1, 2 * height - 1)]);
+
+                // all cells connected by one passage less than cells, a spanning tree
+                let reachable = maze.reachable_cells().unwrap();
+                assert_eq!(
+                    reachable.len(),
+                    2 * width * height - 1,
+                    "generator {}, seed {}",
+                    index,
+                    seed
+                );
+                for y in 0..height {
+                    for x in 0..width {
+                        assert!(reachable.contains(&(2 * x + 1, 2 * y + 1)));
+                    }
+                }
+                assert!(maze.find_path().unwrap().is_some());
+
+                // passages across the edges
+                let text = maze.to_string();
+                let rows: Vec<Vec<char>> = text.lines().map(|row| row.chars().collect()).collect();
+                wrapping += rows.iter().filter(|row| row[0] != '⬜').count();
+                wrapping += rows[0].iter().filter(|&&c| c != '⬜').count();
+            }
+        }
+    }
+    assert!(wrapping > 0);
+}
+
+#[test]
+fn torus_generators_need_two_cells_per_side() {
+    assert_eq!(
+        RecursiveBacktracker::new(1)
+            .generate_with_topology(1, 4, Topology::Torus)
+            .unwrap_err(),
+        MazeError::TooSmall {
+            width: 2,
+            height: 8
+        }
+    );
+    assert_eq!(
+        Kruskal::new(1)
+            .generate_with_topology(0, 4, Topology::Torus)
+            .unwrap_err(),
+        MazeError::TooSmall {
+            width: 0,
+            height: 8
+        }
+    );
+}